@@ -0,0 +1,418 @@
+use std::convert::TryInto;
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT as G;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use near_sdk::env;
+
+/// Exponential ElGamal ciphertext `(r·G, r·H + m·G)` of a small number `m` under public key `H`.
+///
+/// Ciphertexts are additively homomorphic: sum of ciphertexts encrypts sum of their numbers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ciphertext {
+    pub a: RistrettoPoint,
+    pub b: RistrettoPoint,
+}
+
+impl Ciphertext {
+    /// Encryption of zero without any randomness, neutral element of ciphertexts addition.
+    pub fn zero() -> Self {
+        Self {
+            a: RistrettoPoint::identity(),
+            b: RistrettoPoint::identity(),
+        }
+    }
+
+    /// Parses 64 bytes: compressed `a` followed by compressed `b`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 64 {
+            return None;
+        }
+        Some(Self {
+            a: decode_point(&bytes[..32])?,
+            b: decode_point(&bytes[32..])?,
+        })
+    }
+
+    pub fn to_bytes(self) -> [u8; 64] {
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(self.a.compress().as_bytes());
+        bytes[32..].copy_from_slice(self.b.compress().as_bytes());
+        bytes
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self {
+            a: self.a + other.a,
+            b: self.b + other.b,
+        }
+    }
+}
+
+/// Parses compressed Ristretto point.
+pub fn decode_point(bytes: &[u8]) -> Option<RistrettoPoint> {
+    CompressedRistretto::from_slice(bytes).ok()?.decompress()
+}
+
+/// Parses canonically encoded scalar.
+fn decode_scalar(bytes: &[u8]) -> Option<Scalar> {
+    Option::from(Scalar::from_canonical_bytes(bytes.try_into().ok()?))
+}
+
+/// Fiat-Shamir challenge: `sha256(tag || context || points)` reduced modulo group order.
+fn challenge(tag: &[u8], context: &[u8], points: &[RistrettoPoint]) -> Scalar {
+    let mut data = [tag, context].concat();
+    for point in points {
+        data.extend(point.compress().as_bytes());
+    }
+    Scalar::from_bytes_mod_order(env::sha256(&data).try_into().unwrap())
+}
+
+/// Checks Chaum-Pedersen proof `c || s` that `log_g1(y1) == log_g2(y2)`.
+fn verify_equal_logs(
+    tag: &[u8],
+    context: &[u8],
+    (g1, y1): (RistrettoPoint, RistrettoPoint),
+    (g2, y2): (RistrettoPoint, RistrettoPoint),
+    proof: &[u8],
+) -> bool {
+    if proof.len() != 64 {
+        return false;
+    }
+    let (c, s) = match (decode_scalar(&proof[..32]), decode_scalar(&proof[32..])) {
+        (Some(c), Some(s)) => (c, s),
+        _ => return false,
+    };
+    let t1 = s * g1 - c * y1;
+    let t2 = s * g2 - c * y2;
+    c == challenge(tag, context, &[g1, y1, g2, y2, t1, t2])
+}
+
+/// Checks disjunctive Chaum-Pedersen proof `c0 || s0 || c1 || s1`
+/// that ciphertext encrypts either `0` or `1` under `key`.
+fn verify_binary(
+    context: &[u8],
+    key: RistrettoPoint,
+    ciphertext: &Ciphertext,
+    proof: &[u8],
+) -> bool {
+    if proof.len() != 128 {
+        return false;
+    }
+    let scalars: Option<Vec<Scalar>> = proof.chunks(32).map(decode_scalar).collect();
+    let (c0, s0, c1, s1) = match scalars.as_deref() {
+        Some(&[c0, s0, c1, s1]) => (c0, s0, c1, s1),
+        _ => return false,
+    };
+    let Ciphertext { a, b } = *ciphertext;
+    let t0a = s0 * G - c0 * a;
+    let t0b = s0 * key - c0 * b;
+    let t1a = s1 * G - c1 * a;
+    let t1b = s1 * key - c1 * (b - G);
+    c0 + c1 == challenge(b"binary", context, &[key, a, b, t0a, t0b, t1a, t1b])
+}
+
+/// Checks that ballot encrypts exactly one choice.
+///
+/// Every ciphertext should have a proof of encrypting `0` or `1`
+/// and `sum_proof` should prove that their sum encrypts `1`,
+/// i.e. `log_G(Σa) == log_H(Σb - G)`.
+/// All proofs are bound to `context`, so ballots can't be replayed in other elections or by other voters.
+pub fn verify_ballot(
+    context: &[u8],
+    key: RistrettoPoint,
+    ciphertexts: &[Ciphertext],
+    proofs: &[Vec<u8>],
+    sum_proof: &[u8],
+) -> bool {
+    if ciphertexts.len() != proofs.len() {
+        return false;
+    }
+    let sum = ciphertexts
+        .iter()
+        .fold(Ciphertext::zero(), |sum, ciphertext| sum.add(ciphertext));
+    ciphertexts
+        .iter()
+        .zip(proofs)
+        .all(|(ciphertext, proof)| verify_binary(context, key, ciphertext, proof))
+        && verify_equal_logs(b"sum", context, (G, sum.a), (key, sum.b - G), sum_proof)
+}
+
+/// Checks trustee's partial decryption `share = x·a` of ciphertext `(a, b)`,
+/// where `x` is trustee's secret key share, i.e. `log_G(public_share) == log_a(share)`.
+pub fn verify_decryption(
+    context: &[u8],
+    public_share: RistrettoPoint,
+    ciphertext: &Ciphertext,
+    share: RistrettoPoint,
+    proof: &[u8],
+) -> bool {
+    verify_equal_logs(
+        b"decryption",
+        context,
+        (G, public_share),
+        (ciphertext.a, share),
+        proof,
+    )
+}
+
+/// Lagrange interpolation "in the exponent" of points `f(i)·P` at `x`.
+///
+/// `shares` are pairs of distinct non-zero indices and points.
+pub fn interpolate(shares: &[(u64, RistrettoPoint)], x: u64) -> RistrettoPoint {
+    let x = Scalar::from(x);
+    shares
+        .iter()
+        .map(|(i, point)| {
+            let xi = Scalar::from(*i);
+            let coefficient: Scalar = shares
+                .iter()
+                .filter(|(j, _)| j != i)
+                .map(|(j, _)| (x - Scalar::from(*j)) * (xi - Scalar::from(*j)).invert())
+                .product();
+            coefficient * point
+        })
+        .sum()
+}
+
+/// Checks that public key shares come from a single polynomial of degree `threshold - 1`
+/// with `public_key` as its free term, so that any `threshold` trustees could decrypt.
+///
+/// Share index is its position plus one.
+pub fn consistent_shares(
+    public_key: RistrettoPoint,
+    shares: &[RistrettoPoint],
+    threshold: usize,
+) -> bool {
+    let base: Vec<(u64, RistrettoPoint)> =
+        (1..).zip(shares.iter().take(threshold).cloned()).collect();
+    interpolate(&base, 0) == public_key
+        && (1..)
+            .zip(shares)
+            .skip(threshold)
+            .all(|(i, share)| interpolate(&base, i) == *share)
+}
+
+/// Checks that ciphertext encrypts `votes`,
+/// given partial decryptions of at least threshold trustees.
+pub fn verify_tally(
+    ciphertext: &Ciphertext,
+    shares: &[(u64, RistrettoPoint)],
+    votes: u128,
+) -> bool {
+    Scalar::from(votes) * G == ciphertext.b - interpolate(shares, 0)
+}
+
+/// Client side of the protocol. Used to prepare test data.
+#[cfg(test)]
+pub mod client {
+    use super::*;
+
+    /// Deterministic pseudo-random scalar.
+    pub fn scalar(seed: &[u8]) -> Scalar {
+        Scalar::from_bytes_mod_order(env::sha256(seed).try_into().unwrap())
+    }
+
+    /// Generates combined public key and secret key shares of trustees.
+    pub fn trustee_keys(trustees: u64, threshold: usize) -> (RistrettoPoint, Vec<Scalar>) {
+        let coefficients: Vec<Scalar> = (0..threshold)
+            .map(|i| scalar(&[b"coefficient".as_ref(), &[i as u8]].concat()))
+            .collect();
+        let shares = (1..=trustees)
+            .map(|i| {
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(Scalar::ZERO, |value, coefficient| {
+                        value * Scalar::from(i) + coefficient
+                    })
+            })
+            .collect();
+        (coefficients[0] * G, shares)
+    }
+
+    pub fn public_share(secret: &Scalar) -> RistrettoPoint {
+        secret * G
+    }
+
+    fn prove_equal_logs(
+        tag: &[u8],
+        context: &[u8],
+        secret: Scalar,
+        (g1, y1): (RistrettoPoint, RistrettoPoint),
+        (g2, y2): (RistrettoPoint, RistrettoPoint),
+    ) -> Vec<u8> {
+        let w = scalar(&[tag, context, b"nonce"].concat());
+        let c = challenge(tag, context, &[g1, y1, g2, y2, w * g1, w * g2]);
+        [c.to_bytes(), (w + c * secret).to_bytes()].concat()
+    }
+
+    fn prove_binary(
+        context: &[u8],
+        key: RistrettoPoint,
+        ciphertext: &Ciphertext,
+        r: Scalar,
+        m: bool,
+    ) -> Vec<u8> {
+        let Ciphertext { a, b } = *ciphertext;
+        let seed = ciphertext.to_bytes();
+        let w = scalar(&[&seed[..], b"w"].concat());
+        let fake_c = scalar(&[&seed[..], b"c"].concat());
+        let fake_s = scalar(&[&seed[..], b"s"].concat());
+        // Real branch is proven honestly, the other one is simulated.
+        let fake_b = if m { b } else { b - G };
+        let fake_t = (fake_s * G - fake_c * a, fake_s * key - fake_c * fake_b);
+        let real_t = (w * G, w * key);
+        let (t0, t1) = if m {
+            (fake_t, real_t)
+        } else {
+            (real_t, fake_t)
+        };
+        let c = challenge(b"binary", context, &[key, a, b, t0.0, t0.1, t1.0, t1.1]);
+        let real_c = c - fake_c;
+        let real_s = w + real_c * r;
+        let (c0, s0, c1, s1) = if m {
+            (fake_c, fake_s, real_c, real_s)
+        } else {
+            (real_c, real_s, fake_c, fake_s)
+        };
+        [c0.to_bytes(), s0.to_bytes(), c1.to_bytes(), s1.to_bytes()].concat()
+    }
+
+    /// Encrypts choice of one of the candidates. Returns ciphertexts, their proofs and sum proof.
+    pub fn encrypt_ballot(
+        context: &[u8],
+        key: RistrettoPoint,
+        candidates: usize,
+        choice: usize,
+    ) -> (Vec<Ciphertext>, Vec<Vec<u8>>, Vec<u8>) {
+        let randomness: Vec<Scalar> = (0..candidates)
+            .map(|i| scalar(&[context, b"r", &[i as u8]].concat()))
+            .collect();
+        let ciphertexts: Vec<Ciphertext> = randomness
+            .iter()
+            .enumerate()
+            .map(|(i, r)| Ciphertext {
+                a: r * G,
+                b: r * key
+                    + if i == choice {
+                        G
+                    } else {
+                        RistrettoPoint::identity()
+                    },
+            })
+            .collect();
+        let proofs = ciphertexts
+            .iter()
+            .zip(&randomness)
+            .enumerate()
+            .map(|(i, (ciphertext, r))| prove_binary(context, key, ciphertext, *r, i == choice))
+            .collect();
+        let r: Scalar = randomness.iter().sum();
+        let sum_proof = prove_equal_logs(b"sum", context, r, (G, r * G), (key, r * key));
+        (ciphertexts, proofs, sum_proof)
+    }
+
+    /// Partial decryption of a ciphertext with its proof.
+    pub fn decrypt_share(
+        context: &[u8],
+        secret: Scalar,
+        ciphertext: &Ciphertext,
+    ) -> (RistrettoPoint, Vec<u8>) {
+        let share = secret * ciphertext.a;
+        let proof = prove_equal_logs(
+            b"decryption",
+            context,
+            secret,
+            (G, secret * G),
+            (ciphertext.a, share),
+        );
+        (share, proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::client::*;
+    use super::*;
+
+    #[test]
+    fn should_verify_ballot_proofs() {
+        let (key, _) = trustee_keys(3, 2);
+
+        let (ciphertexts, proofs, sum_proof) = encrypt_ballot(b"voter", key, 3, 1);
+
+        assert!(verify_ballot(
+            b"voter",
+            key,
+            &ciphertexts,
+            &proofs,
+            &sum_proof
+        ));
+        assert!(!verify_ballot(
+            b"other voter",
+            key,
+            &ciphertexts,
+            &proofs,
+            &sum_proof
+        ));
+    }
+
+    #[test]
+    fn should_reject_ballot_with_several_choices() {
+        let (key, _) = trustee_keys(3, 2);
+        let (first, first_proofs, sum_proof) = encrypt_ballot(b"voter", key, 2, 0);
+        let (second, second_proofs, _) = encrypt_ballot(b"voter", key, 2, 1);
+
+        let ciphertexts = vec![first[0], second[1]];
+        let proofs = vec![first_proofs[0].clone(), second_proofs[1].clone()];
+
+        assert!(!verify_ballot(
+            b"voter",
+            key,
+            &ciphertexts,
+            &proofs,
+            &sum_proof
+        ));
+    }
+
+    #[test]
+    fn should_check_public_key_shares() {
+        let (key, secrets) = trustee_keys(4, 3);
+        let mut shares: Vec<RistrettoPoint> = secrets.iter().map(public_share).collect();
+
+        assert!(consistent_shares(key, &shares, 3));
+        assert!(!consistent_shares(key, &shares, 2));
+        shares[3] += G;
+        assert!(!consistent_shares(key, &shares, 3));
+    }
+
+    #[test]
+    fn should_verify_tally_with_threshold_of_trustees() {
+        let (key, secrets) = trustee_keys(3, 2);
+        let tally = (0..5)
+            .map(|i| encrypt_ballot(&[i], key, 2, usize::from(i % 2)).0[0])
+            .fold(Ciphertext::zero(), |sum, ciphertext| sum.add(&ciphertext));
+
+        let shares: Vec<(u64, RistrettoPoint)> = [1, 3]
+            .iter()
+            .map(|i| {
+                let secret = secrets[*i as usize - 1];
+                let (share, proof) = decrypt_share(b"tally", secret, &tally);
+                assert!(verify_decryption(
+                    b"tally",
+                    public_share(&secret),
+                    &tally,
+                    share,
+                    &proof
+                ));
+                (*i, share)
+            })
+            .collect();
+
+        assert!(verify_tally(&tally, &shares, 3));
+        assert!(!verify_tally(&tally, &shares, 2));
+        assert!(!verify_tally(&tally, &shares[..1], 3));
+    }
+}
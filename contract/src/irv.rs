@@ -0,0 +1,170 @@
+use std::cmp::Reverse;
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+
+use crate::CandidateId;
+
+/// Single counting round of a ranked ballots tally.
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq)]
+pub struct Round {
+    /// First-preference votes of every candidate still in the race.
+    pub tallies: Vec<(CandidateId, u128)>,
+    /// Ballots without any continuing candidate left.
+    pub exhausted: u128,
+    /// Candidates elected at the end of the round.
+    pub elected: Vec<CandidateId>,
+    /// Candidate dropped at the end of the round.
+    pub eliminated: Option<CandidateId>,
+    /// Candidates tied for the election or elimination of the round, broken by their ranks.
+    pub tied: Vec<CandidateId>,
+}
+
+/// Candidates sharing `votes` if there are more than one of them.
+pub fn tied_with(tallies: &[(CandidateId, u128)], votes: u128) -> Vec<CandidateId> {
+    let tied: Vec<CandidateId> = tallies
+        .iter()
+        .filter(|(_, v)| *v == votes)
+        .map(|(c, _)| *c)
+        .collect();
+    if tied.len() > 1 {
+        tied
+    } else {
+        vec![]
+    }
+}
+
+/// Runs instant-runoff rounds over ranked ballots, grouped by ranking with their counts.
+///
+/// Each round counts ballots for their highest ranked continuing candidate.
+/// Candidate with a majority of non-exhausted ballots wins,
+/// otherwise the weakest candidate is eliminated and the count repeats.
+/// Ties for elimination drop the candidate with the highest `rank`.
+///
+/// Returns winner (if any ballot was cast) and all counting rounds.
+pub fn instant_runoff(
+    candidates: usize,
+    ballots: &[(Vec<CandidateId>, u128)],
+    rank: &[usize],
+) -> (Option<CandidateId>, Vec<Round>) {
+    let total: u128 = ballots.iter().map(|(_, count)| count).sum();
+    let mut continuing = vec![true; candidates];
+    let mut rounds = vec![];
+    loop {
+        let mut counts = vec![0u128; candidates];
+        let mut exhausted = 0;
+        for (ranking, count) in ballots {
+            match ranking.iter().find(|c| continuing[usize::from(**c)]) {
+                Some(choice) => counts[usize::from(*choice)] += count,
+                None => exhausted += count,
+            }
+        }
+        let tallies: Vec<(CandidateId, u128)> = (0..candidates)
+            .filter(|i| continuing[*i])
+            .map(|i| (i as CandidateId, counts[i]))
+            .collect();
+        let active = total - exhausted;
+
+        let leader = tallies
+            .iter()
+            .rev()
+            .max_by_key(|(_, votes)| *votes)
+            .map(|(candidate, votes)| (*candidate, *votes));
+        let winner = match leader {
+            _ if active == 0 => Some(None),
+            Some((candidate, votes)) if votes * 2 > active => Some(Some(candidate)),
+            _ => None,
+        };
+        if let Some(winner) = winner {
+            rounds.push(Round {
+                tallies,
+                exhausted,
+                elected: winner.into_iter().collect(),
+                eliminated: None,
+                tied: vec![],
+            });
+            return (winner, rounds);
+        }
+
+        let (eliminated, fewest) = *tallies
+            .iter()
+            .max_by_key(|(candidate, votes)| (Reverse(*votes), rank[usize::from(*candidate)]))
+            .unwrap();
+        continuing[usize::from(eliminated)] = false;
+        rounds.push(Round {
+            tied: tied_with(&tallies, fewest),
+            tallies,
+            exhausted,
+            elected: vec![],
+            eliminated: Some(eliminated),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_elect_majority_winner_in_first_round() {
+        let ballots = vec![(vec![0, 1], 1), (vec![0, 2], 1), (vec![1, 0], 1)];
+
+        let (winner, rounds) = instant_runoff(3, &ballots, &[0, 1, 2]);
+
+        assert_eq!(winner, Some(0));
+        assert_eq!(
+            rounds,
+            vec![Round {
+                tallies: vec![(0, 2), (1, 1), (2, 0)],
+                exhausted: 0,
+                elected: vec![0],
+                eliminated: None,
+                tied: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    fn should_transfer_votes_of_eliminated_candidates() {
+        let ballots = vec![(vec![0], 2), (vec![1, 2], 1), (vec![1], 1), (vec![2, 1], 1)];
+
+        let (winner, rounds) = instant_runoff(3, &ballots, &[0, 1, 2]);
+
+        assert_eq!(winner, Some(1));
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[0].eliminated, Some(2));
+        assert_eq!(rounds[1].tallies, vec![(0, 2), (1, 3)]);
+    }
+
+    #[test]
+    fn should_count_exhausted_ballots() {
+        let ballots = vec![(vec![0], 2), (vec![1], 1), (vec![2], 1), (vec![2, 1], 1)];
+
+        let (winner, rounds) = instant_runoff(3, &ballots, &[0, 1, 2]);
+
+        assert_eq!(winner, Some(0));
+        assert_eq!(rounds[0].eliminated, Some(1));
+        assert_eq!(rounds[1].tallies, vec![(0, 2), (2, 2)]);
+        assert_eq!(rounds[1].eliminated, Some(2));
+        assert_eq!(rounds[2].tallies, vec![(0, 2)]);
+        assert_eq!(rounds[2].exhausted, 3);
+    }
+
+    #[test]
+    fn should_eliminate_tied_candidate_by_rank() {
+        let ballots = vec![(vec![0], 2), (vec![1, 0], 1), (vec![2, 0], 1)];
+
+        let (winner, rounds) = instant_runoff(3, &ballots, &[0, 2, 1]);
+
+        assert_eq!(winner, Some(0));
+        assert_eq!(rounds[0].eliminated, Some(1));
+        assert_eq!(rounds[0].tied, vec![1, 2]);
+    }
+
+    #[test]
+    fn should_not_elect_anyone_without_ballots() {
+        let (winner, rounds) = instant_runoff(2, &[], &[0, 1]);
+
+        assert_eq!(winner, None);
+        assert_eq!(rounds.len(), 1);
+    }
+}
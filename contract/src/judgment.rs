@@ -0,0 +1,80 @@
+use std::cmp::Ordering;
+
+/// Grade at the given position of the sorted grades.
+fn grade_at(histogram: &[u128], position: u128) -> usize {
+    let mut seen = 0;
+    for (grade, count) in histogram.iter().enumerate() {
+        seen += count;
+        if position < seen {
+            return grade;
+        }
+    }
+    unreachable!("Position is outside of the histogram")
+}
+
+/// Majority values of a candidate: lower median grade,
+/// then lower median of the remaining grades once it's removed and so on.
+pub fn majority_values(histogram: &[u128]) -> impl Iterator<Item = usize> + '_ {
+    let total: u128 = histogram.iter().sum();
+    // Grades left of `left` and right of `right` positions (both inclusive) are not removed yet.
+    let mut left = total.checked_sub(1).map(|last| last / 2 + 1).unwrap_or(0);
+    let mut right = left;
+    std::iter::from_fn(move || {
+        let left_size = left;
+        let right_size = total - right;
+        if left_size == 0 && right_size == 0 {
+            None
+        } else if left_size >= right_size {
+            left -= 1;
+            Some(grade_at(histogram, left))
+        } else {
+            right += 1;
+            Some(grade_at(histogram, right - 1))
+        }
+    })
+}
+
+/// Lower median grade. `None` if there are no grades at all.
+pub fn median_grade(histogram: &[u128]) -> Option<usize> {
+    majority_values(histogram).next()
+}
+
+/// Compares candidates by their majority values, better candidate is greater.
+///
+/// Equal median grades are resolved with the standard majority judgment tie-breaking:
+/// median grades are removed one by one until medians differ.
+pub fn compare(a: &[u128], b: &[u128]) -> Ordering {
+    majority_values(a).cmp(majority_values(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_list_majority_values() {
+        let histogram = [1, 2, 0, 1];
+
+        let values: Vec<usize> = majority_values(&histogram).collect();
+
+        assert_eq!(values, vec![1, 1, 0, 3]);
+    }
+
+    #[test]
+    fn should_take_lower_median() {
+        assert_eq!(median_grade(&[0, 1, 1]), Some(1));
+        assert_eq!(median_grade(&[1, 0, 2]), Some(2));
+        assert_eq!(median_grade(&[0, 0, 0]), None);
+    }
+
+    #[test]
+    fn should_break_median_ties() {
+        let a = [1, 2, 1];
+        let b = [0, 3, 1];
+        let c = [1, 2, 1];
+
+        assert_eq!(median_grade(&a), median_grade(&b));
+        assert_eq!(compare(&a, &b), Ordering::Less);
+        assert_eq!(compare(&a, &c), Ordering::Equal);
+    }
+}
@@ -2,13 +2,91 @@ use std::convert::TryInto;
 
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::{LookupMap, LookupSet};
+use near_sdk::json_types::{Base64VecU8, U128};
 use near_sdk::serde::{Deserialize, Serialize};
-use near_sdk::{env, near_bindgen, AccountId, BorshStorageKey, PanicOnDefault};
+use near_sdk::{
+    env, ext_contract, near_bindgen, AccountId, BorshStorageKey, Gas, PanicOnDefault, Promise,
+    PromiseError,
+};
+
+mod elgamal;
+mod irv;
+mod judgment;
+mod membership;
+mod merkle;
+mod rsa;
+mod schulze;
+mod stv;
+mod voucher;
+
+use callbacks::ext_self;
 
 const CREATE_ELECTION_COST: u128 = 1; // NEAR
 
 const NOT_REGISTERED_ERROR: &str = "Account is not registered as a valid organization.";
+const NOT_FOUND_ERROR: &str = "Election not found";
+const HIDDEN_RESULTS_ERROR: &str = "Election results are not visible yet";
 const CANDIDATES_LIMIT: u16 = 256;
+/// Schulze elections update `n²` pairwise counters on every ballot.
+const SCHULZE_CANDIDATES_LIMIT: u16 = 32;
+/// Dowdall points are scaled by `lcm(1..=n)` of candidates count, about `5·10¹⁵` for 40.
+const DOWDALL_CANDIDATES_LIMIT: u16 = 40;
+/// Instant runoff and STV elections load every distinct ranking, up to `n` candidates long,
+/// when finalized.
+const RANKED_CANDIDATES_LIMIT: u16 = 20;
+/// Fixed-point precision of fractional votes. Whole vote equals `VOTE_SCALE` units.
+const VOTE_SCALE: u128 = 1_000_000;
+/// Delegates followed at most from a delegator, so that counting stays bounded.
+const MAX_DELEGATION_DEPTH: usize = 10;
+const FT_BALANCE_OF_GAS: Gas = Gas(5_000_000_000_000);
+const NFT_TOKEN_GAS: Gas = Gas(5_000_000_000_000);
+const VOTE_CALLBACK_GAS: Gas = Gas(10_000_000_000_000);
+
+/// NEP-141 fungible token contract.
+#[ext_contract(ext_ft)]
+trait FungibleToken {
+    fn ft_balance_of(&self, account_id: AccountId) -> U128;
+}
+
+/// NEP-171 non-fungible token contract.
+#[ext_contract(ext_nft)]
+trait NonFungibleToken {
+    fn nft_token(&self, token_id: String) -> Option<NftToken>;
+}
+
+/// Callbacks of this contract, wrapped as generated functions take many arguments.
+#[allow(clippy::too_many_arguments)]
+mod callbacks {
+    use super::*;
+
+    #[ext_contract(ext_self)]
+    pub trait VoteCallbacks {
+        fn on_token_balance(
+            &mut self,
+            organization_id: OrganizationId,
+            election_id: String,
+            candidate_id: u8,
+            voter_id: VoterId,
+        );
+
+        fn on_nft_token(
+            &mut self,
+            organization_id: OrganizationId,
+            election_id: String,
+            candidate_id: u8,
+            token_id: String,
+            voter_id: VoterId,
+        );
+    }
+}
+
+/// Part of NEP-171 token view needed to check ownership.
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftToken {
+    token_id: String,
+    owner_id: AccountId,
+}
 
 /// Contract for performing public elections between values.
 #[near_bindgen]
@@ -18,7 +96,47 @@ pub struct Elections {
     organizations: LookupMap<OrganizationId, ElectionCount>,
     elections: LookupMap<(OrganizationId, ElectionId), Election>,
     votes: LookupMap<(OrganizationId, ElectionId, CandidateId), u128>,
+    /// Accounts voted, keyed as before other voter identities were supported.
     voters: LookupSet<(OrganizationId, ElectionId, VoterId)>,
+    /// Voter identities other than accounts.
+    ballot_voters: LookupSet<(OrganizationId, ElectionId, Voter)>,
+    ballots_count: LookupMap<(OrganizationId, ElectionId), BallotId>,
+    /// Distinct rankings of an election, indexed from 0 in order of the first ballot with them.
+    rankings: LookupMap<(OrganizationId, ElectionId, u64), RankingTally>,
+    ranking_ids: LookupMap<(OrganizationId, ElectionId, Vec<CandidateId>), u64>,
+    rankings_counts: LookupMap<(OrganizationId, ElectionId), u64>,
+    results: LookupMap<(OrganizationId, ElectionId), ElectionResult>,
+    scores: LookupMap<(OrganizationId, ElectionId, CandidateId), ScoreTally>,
+    pairwise: LookupMap<(OrganizationId, ElectionId), Vec<u32>>,
+    grades: LookupMap<(OrganizationId, ElectionId, CandidateId), Vec<u128>>,
+    commitments: LookupMap<(OrganizationId, ElectionId, VoterId), Vec<u8>>,
+    committers: LookupMap<(OrganizationId, ElectionId, BallotId), VoterId>,
+    encrypted_tallies: LookupMap<(OrganizationId, ElectionId), Vec<[u8; 64]>>,
+    decryption_shares: LookupMap<(OrganizationId, ElectionId, AccountId), Vec<[u8; 32]>>,
+    /// Encrypted elections with verified plaintext tallies.
+    decrypted_tallies: LookupSet<(OrganizationId, ElectionId)>,
+    allowlists: LookupMap<(OrganizationId, ElectionId, u64), VoterId>,
+    allowlist_positions: LookupMap<(OrganizationId, ElectionId, VoterId), u64>,
+    allowlist_sizes: LookupMap<(OrganizationId, ElectionId), u64>,
+    voucher_keys: LookupMap<OrganizationId, [u8; 32]>,
+    /// History of delegations for a single election or, with `None`, for every election
+    /// of an organization. Changes of an account are indexed from 0 in order they are made.
+    delegations: LookupMap<(OrganizationId, Option<ElectionId>, VoterId, u64), Delegation>,
+    delegation_changes: LookupMap<(OrganizationId, Option<ElectionId>, VoterId), u64>,
+    /// Accounts with delegations in a scope, indexed from 0 in order of their first delegation.
+    delegators: LookupMap<(OrganizationId, Option<ElectionId>, u64), VoterId>,
+    delegators_counts: LookupMap<(OrganizationId, Option<ElectionId>), u64>,
+    /// Numbers of election's and organization-wide delegators counted in an election.
+    counted_delegators: LookupMap<(OrganizationId, ElectionId), (u64, u64)>,
+    /// Current choices of voters in elections with plain ballots cast by accounts.
+    choices: LookupMap<(OrganizationId, ElectionId, VoterId), Choice>,
+    blank_votes: LookupMap<(OrganizationId, ElectionId, BlankOption), u128>,
+    /// Ballots for a candidate of a plurality election in the order they were cast:
+    /// index of the earliest one still counted and number of ballots listed.
+    first_votes: LookupMap<(OrganizationId, ElectionId, CandidateId), BallotList>,
+    candidate_ballots: LookupMap<(OrganizationId, ElectionId, CandidateId, u64), ListedBallot>,
+    /// Number of revealed ballots of an election with secret ballots.
+    revealed_counts: LookupMap<(OrganizationId, ElectionId), u128>,
 }
 
 type OrganizationId = AccountId;
@@ -26,6 +144,112 @@ type ElectionCount = u128;
 type ElectionId = u128;
 type CandidateId = u8;
 type VoterId = AccountId;
+type BallotId = u128;
+
+/// Identity a ballot is cast under. Every identity could vote only once in an election.
+#[derive(BorshDeserialize, BorshSerialize)]
+enum Voter {
+    Account(VoterId),
+    /// Nullifier of an anonymous voter, canonical BN254 scalar field element.
+    Nullifier([u8; 32]),
+    /// `sha256` of a spent blind-signed credential token.
+    Token([u8; 32]),
+    /// Id of an NFT pass ballot is cast with.
+    Nft(String),
+}
+
+/// Change of voter's delegation.
+///
+/// Changes are kept, so that elections are counted with delegations active at their end.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct Delegation {
+    /// Account ballot is given to, `None` once delegation is revoked.
+    delegate: Option<VoterId>,
+    /// Time of the change. Counts only in elections ended after it.
+    since: u64,
+}
+
+/// Plain ballot of a voter, could be changed or retracted until election end.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct Choice {
+    option: BallotOption,
+    /// Number of votes ballot counts as.
+    weight: u128,
+    /// Position of the ballot in the list of its candidate, if ballots are listed to break ties.
+    listed: Option<u64>,
+}
+
+/// Ballots for a candidate still counted, linked in the order they were cast.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+struct BallotList {
+    first: Option<u64>,
+    last: Option<u64>,
+    /// Number of ballots ever listed, so position of the next one.
+    listed: u64,
+}
+
+/// Ballot for a candidate listed to find the earliest one still counted.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct ListedBallot {
+    cast_at: u64,
+    previous: Option<u64>,
+    next: Option<u64>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy)]
+enum BallotOption {
+    Candidate(CandidateId),
+    Blank(BlankOption),
+}
+
+/// Option of a ballot not counted toward any candidate.
+#[derive(
+    BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum BlankOption {
+    Abstain,
+    /// None of the above.
+    Nota,
+}
+
+/// How caller's ballot is cast in an election.
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum VoteStatus {
+    NotVoted,
+    /// Caller voted themselves.
+    Direct,
+    /// Caller didn't vote, but their delegate (maybe through other delegates) did.
+    Delegated,
+}
+
+/// RSA public key organization blind-signs voter credentials with. Numbers are big-endian.
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+struct IssuerKey {
+    modulus: Vec<u8>,
+    exponent: Vec<u8>,
+}
+
+/// Contract state before elections got settings, see [migrate](Elections::migrate).
+#[derive(BorshDeserialize, BorshSerialize)]
+struct LegacyElections {
+    owner_id: AccountId,
+    organizations: LookupMap<OrganizationId, ElectionCount>,
+    elections: LookupMap<(OrganizationId, ElectionId), LegacyElection>,
+    votes: LookupMap<(OrganizationId, ElectionId, CandidateId), u128>,
+    voters: LookupSet<(OrganizationId, ElectionId, VoterId)>,
+}
+
+/// Plurality election stored before elections got settings.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct LegacyElection {
+    start: u64,
+    end: u64,
+    title: String,
+    description: String,
+    candidates: Vec<String>,
+}
 
 /// Election data actually stored.
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -35,9 +259,189 @@ struct Election {
     title: String,
     description: String,
     candidates: Vec<String>,
+    kind: ElectionKind,
+    seats: u8,
+    runoff: Option<Runoff>,
+    /// Election this one is a runoff of.
+    parent: Option<ElectionId>,
+    /// End of reveal phase for elections with secret ballots.
+    reveal_end: Option<u64>,
+    results_visibility: ResultsVisibility,
+    show_turnout: bool,
+    /// Results published by the organization.
+    published: bool,
+    /// Trustees decrypting tallies of an election with encrypted ballots.
+    encryption: Option<Encryption>,
+    /// Eligibility of voters casting anonymous ballots.
+    anonymity: Option<Anonymity>,
+    /// Key of credentials blind-signed by the organization ballots are cast with
+    /// instead of accounts.
+    credentials: Option<IssuerKey>,
+    /// Only accounts on the election allowlist could vote.
+    allowlist: bool,
+    /// Merkle root of eligible voters with their weights, see [merkle::leaf].
+    allowlist_root: Option<[u8; 32]>,
+    /// Token holders eligible to vote and their weights.
+    token_gate: Option<TokenGate>,
+    /// NEP-171 contract of NFT passes, every token votes once.
+    nft_contract: Option<AccountId>,
+    /// Voters should present vouchers signed by the organization.
+    vouchers: bool,
+    /// Ballots of voters who didn't vote go to their delegates' choices.
+    delegation: bool,
+    /// Options of ballots not counted toward any candidate.
+    blank_options: Vec<BlankOption>,
+    quorum: Option<Quorum>,
+    majority: Majority,
+    tie_break: TieBreak,
+}
+
+/// Voting by holders of a NEP-141 token.
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+struct TokenGate {
+    token_id: AccountId,
+    /// Balance a voter should hold at the moment of voting.
+    min_balance: u128,
+    weighting: TokenWeighting,
+}
+
+impl TokenGate {
+    /// Number of votes a ballot of a holder with the given balance counts as.
+    fn weight(&self, balance: u128) -> u128 {
+        match self.weighting {
+            TokenWeighting::Balance => balance,
+            TokenWeighting::SquareRoot => balance.isqrt(),
+        }
+    }
+}
+
+/// Defines how token balance of a voter translates into votes.
+#[derive(
+    BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, Default, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum TokenWeighting {
+    /// Ballot counts as many votes as tokens held.
+    #[default]
+    Balance,
+    /// Ballot counts as integer square root of tokens held (quadratic voting).
+    SquareRoot,
+}
+
+/// Anonymous voting settings. Voters prove membership in a Merkle tree of identity commitments.
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+struct Anonymity {
+    /// Merkle root of voters identity commitments, canonical BN254 scalar field element.
+    voters_root: [u8; 32],
+    /// Compressed Groth16 verifying key of the membership circuit.
+    verifying_key: Vec<u8>,
+}
+
+/// Threshold ElGamal settings of an election with encrypted ballots.
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+struct Encryption {
+    /// Combined public key, compressed Ristretto point.
+    public_key: [u8; 32],
+    /// Trustee accounts with their public key shares. Share index is trustee position plus one.
+    trustees: Vec<(AccountId, [u8; 32])>,
+    /// Number of trustees needed to decrypt tallies.
+    threshold: u8,
+}
+
+impl Encryption {
+    fn assert_valid(&self) {
+        assert!(
+            !self.trustees.is_empty(),
+            "At least one trustee should be provided"
+        );
+        assert!(
+            self.trustees.len() <= usize::from(u8::MAX),
+            "Maximum {} trustees expected",
+            u8::MAX
+        );
+        for (i, (account_id, _)) in self.trustees.iter().enumerate() {
+            assert!(
+                !self.trustees[..i]
+                    .iter()
+                    .any(|(other, _)| other == account_id),
+                "Trustee {} listed more than once",
+                account_id
+            );
+        }
+        assert!(
+            (1..=self.trustees.len()).contains(&usize::from(self.threshold)),
+            "Threshold should be between 1 and trustees count"
+        );
+        let public_key = elgamal::decode_point(&self.public_key).expect("Invalid public key");
+        let shares: Vec<_> = self
+            .trustees
+            .iter()
+            .map(|(_, share)| elgamal::decode_point(share).expect("Invalid public key share"))
+            .collect();
+        assert!(
+            elgamal::consistent_shares(public_key, &shares, self.threshold.into()),
+            "Public key shares don't match the public key"
+        );
+    }
+}
+
+/// Defines when vote counts of an election become visible.
+#[derive(
+    BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, Default, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum ResultsVisibility {
+    /// Running tallies are always visible.
+    #[default]
+    Live,
+    /// Counts are visible once election (including its reveal phase) has ended.
+    AfterEnd,
+    /// Counts are visible once organization publishes them after election end.
+    AfterPublish,
+}
+
+/// Settings of an automatic second round.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct Runoff {
+    /// Percent of votes a candidate should exceed to win in the first round.
+    threshold: u8,
+    /// Runoff duration in nanoseconds.
+    duration: u64,
 }
 
 impl Election {
+    /// Plurality election with default settings.
+    fn from_legacy(legacy: LegacyElection) -> Self {
+        Self {
+            start: legacy.start,
+            end: legacy.end,
+            title: legacy.title,
+            description: legacy.description,
+            candidates: legacy.candidates,
+            kind: ElectionKind::Plurality,
+            seats: 1,
+            runoff: None,
+            parent: None,
+            reveal_end: None,
+            results_visibility: ResultsVisibility::Live,
+            show_turnout: false,
+            published: false,
+            encryption: None,
+            anonymity: None,
+            credentials: None,
+            allowlist: false,
+            allowlist_root: None,
+            token_gate: None,
+            nft_contract: None,
+            vouchers: false,
+            delegation: false,
+            blank_options: vec![],
+            quorum: None,
+            majority: Majority::Plurality,
+            tie_break: TieBreak::None,
+        }
+    }
+
     fn new(input: &ElectionInput) -> Self {
         Self {
             start: input.start.parse().unwrap(),
@@ -45,6 +449,515 @@ impl Election {
             title: input.title.clone(),
             description: input.description.clone(),
             candidates: input.candidates.clone(),
+            kind: input.kind.clone(),
+            seats: input.seats,
+            runoff: input.runoff.as_ref().map(|runoff| Runoff {
+                threshold: runoff.threshold.unwrap_or(50),
+                duration: runoff.duration.parse().unwrap(),
+            }),
+            parent: None,
+            reveal_end: input.reveal_end.as_ref().map(|end| end.parse().unwrap()),
+            results_visibility: input.results_visibility.clone(),
+            show_turnout: input.show_turnout,
+            published: false,
+            encryption: input.encryption.as_ref().map(|encryption| Encryption {
+                public_key: to_key(&encryption.public_key),
+                trustees: encryption
+                    .trustees
+                    .iter()
+                    .map(|trustee| (trustee.account_id.clone(), to_key(&trustee.public_share)))
+                    .collect(),
+                threshold: encryption
+                    .threshold
+                    .unwrap_or_else(|| encryption.trustees.len().try_into().unwrap_or(u8::MAX)),
+            }),
+            anonymity: input.anonymity.as_ref().map(|anonymity| Anonymity {
+                voters_root: to_key(&anonymity.voters_root),
+                verifying_key: anonymity.verifying_key.0.clone(),
+            }),
+            credentials: input.credentials.as_ref().map(|key| IssuerKey {
+                modulus: key.modulus.0.clone(),
+                exponent: key.exponent.0.clone(),
+            }),
+            allowlist: input.allowlist,
+            allowlist_root: input.allowlist_root.as_ref().map(to_key),
+            token_gate: input.token_gate.as_ref().map(|gate| TokenGate {
+                token_id: gate.token_id.clone(),
+                min_balance: gate
+                    .min_balance
+                    .as_ref()
+                    .map_or(1, |min| min.parse().unwrap()),
+                weighting: gate.weighting.clone(),
+            }),
+            nft_contract: input.nft_contract.clone(),
+            vouchers: input.vouchers,
+            delegation: input.delegation,
+            blank_options: input.blank_options.clone(),
+            quorum: input.quorum.as_ref().map(|quorum| match quorum {
+                QuorumInput::Ballots(ballots) => Quorum::Ballots(ballots.parse().unwrap()),
+                QuorumInput::AllowlistPercent(percent) => Quorum::AllowlistPercent(*percent),
+            }),
+            majority: input.majority.clone(),
+            tie_break: input.tie_break.clone(),
+        }
+    }
+
+    /// Time after which no ballots could be counted anymore.
+    fn counting_end(&self) -> u64 {
+        self.reveal_end.unwrap_or(self.end)
+    }
+
+    fn assert_valid_settings(&self) {
+        assert!(self.seats > 0, "At least one seat should be provided");
+        assert!(
+            usize::from(self.seats) < self.candidates.len(),
+            "Seats should be fewer than candidates"
+        );
+        assert!(
+            self.seats == 1
+                || !matches!(
+                    self.kind,
+                    ElectionKind::InstantRunoff | ElectionKind::Schulze
+                ),
+            "Election kind supports only a single seat"
+        );
+        assert!(
+            self.kind != ElectionKind::Schulze
+                || self.candidates.len() <= SCHULZE_CANDIDATES_LIMIT.into(),
+            "Maximum {} candidates expected in Schulze elections",
+            SCHULZE_CANDIDATES_LIMIT
+        );
+        assert!(
+            self.kind
+                != (ElectionKind::Positional {
+                    rule: PositionalRule::Dowdall,
+                })
+                || self.candidates.len() <= DOWDALL_CANDIDATES_LIMIT.into(),
+            "Maximum {} candidates expected in Dowdall elections",
+            DOWDALL_CANDIDATES_LIMIT
+        );
+        assert!(
+            !matches!(self.kind, ElectionKind::InstantRunoff | ElectionKind::Stv)
+                || self.candidates.len() <= RANKED_CANDIDATES_LIMIT.into(),
+            "Maximum {} candidates expected in instant runoff and STV elections",
+            RANKED_CANDIDATES_LIMIT
+        );
+        if let Some(reveal_end) = self.reveal_end {
+            assert!(
+                self.kind == ElectionKind::Plurality,
+                "Secret ballots are supported only by plurality elections"
+            );
+            assert!(self.end < reveal_end, "End should be before reveal end");
+        }
+        if let Some(encryption) = &self.encryption {
+            assert!(
+                self.kind == ElectionKind::Plurality && self.reveal_end.is_none(),
+                "Encrypted ballots are supported only by plurality elections without secret ballots"
+            );
+            encryption.assert_valid();
+        }
+        if let Some(anonymity) = &self.anonymity {
+            assert!(
+                self.kind == ElectionKind::Plurality
+                    && self.reveal_end.is_none()
+                    && self.encryption.is_none(),
+                "Anonymous ballots are supported only by plurality elections \
+                without secret or encrypted ballots"
+            );
+            assert!(
+                membership::decode_field(&anonymity.voters_root).is_some(),
+                "Invalid voters root"
+            );
+            assert!(
+                membership::decode_verifying_key(&anonymity.verifying_key).is_some(),
+                "Invalid verifying key"
+            );
+        }
+        if let Some(key) = &self.credentials {
+            assert!(
+                self.kind == ElectionKind::Plurality
+                    && self.reveal_end.is_none()
+                    && self.encryption.is_none()
+                    && self.anonymity.is_none(),
+                "Credentials are supported only by plurality elections \
+                without secret, encrypted or anonymous ballots"
+            );
+            assert!(
+                rsa::valid_key(&key.modulus, &key.exponent),
+                "Invalid issuer key"
+            );
+        }
+        assert!(
+            !self.allowlist || (self.anonymity.is_none() && self.credentials.is_none()),
+            "Allowlist is supported only by elections with ballots cast by accounts"
+        );
+        assert!(
+            !self.allowlist || self.allowlist_root.is_none(),
+            "Election could have either allowlist or Merkle allowlist"
+        );
+        let gates = self.gates();
+        for gate in &gates {
+            assert!(
+                self.plain_account_ballots(),
+                "{} is supported only by plurality elections with plain ballots cast by accounts",
+                gate
+            );
+        }
+        assert!(
+            gates.len() <= 1,
+            "Election could have only one of Merkle allowlist, token gate or NFT gate"
+        );
+        if let Some(token_gate) = &self.token_gate {
+            assert!(
+                token_gate.min_balance > 0,
+                "Minimum token balance should be positive"
+            );
+        }
+        // Ballots of token and NFT gated elections are counted in callbacks.
+        let callback_ballots = self.token_gate.is_some() || self.nft_contract.is_some();
+        assert!(
+            !self.vouchers || (self.plain_account_ballots() && !callback_ballots),
+            "Vouchers are supported only by plurality elections \
+            with plain ballots cast by accounts without token or NFT gate"
+        );
+        assert!(
+            !self.delegation
+                || (self.plain_account_ballots() && gates.is_empty() && !self.vouchers),
+            "Delegation is supported only by plurality elections \
+            with plain unweighted ballots cast by accounts without gates or vouchers"
+        );
+        if !self.blank_options.is_empty() {
+            assert!(
+                self.plain_account_ballots() && !callback_ballots,
+                "Blank ballots are supported only by plurality elections \
+                with plain ballots cast by accounts without token or NFT gate"
+            );
+            for (i, option) in self.blank_options.iter().enumerate() {
+                assert!(
+                    !self.blank_options[..i].contains(option),
+                    "Blank option {:?} listed more than once",
+                    option
+                );
+            }
+        }
+        match self.quorum {
+            Some(Quorum::Ballots(ballots)) => assert!(ballots > 0, "Quorum should be positive"),
+            Some(Quorum::AllowlistPercent(percent)) => {
+                assert!(self.allowlist, "Allowlist quorum requires an allowlist");
+                assert!(
+                    (1..=100).contains(&percent),
+                    "Quorum percent should be between 1 and 100"
+                );
+            }
+            None => {}
+        }
+        assert!(
+            self.majority == Majority::Plurality
+                || (self.kind == ElectionKind::Plurality
+                    && self.seats == 1
+                    && self.runoff.is_none()),
+            "Majority threshold is supported only by single seat plurality elections without runoff"
+        );
+        assert!(
+            matches!(
+                self.tie_break,
+                TieBreak::None | TieBreak::Random | TieBreak::CandidateOrder
+            ) || !matches!(self.kind, ElectionKind::InstantRunoff | ElectionKind::Stv),
+            "Only random and candidate order tie-breaks are supported by instant runoff \
+            and STV elections"
+        );
+        assert!(
+            self.tie_break != TieBreak::FirstVote
+                || (self.kind == ElectionKind::Plurality
+                    && self.reveal_end.is_none()
+                    && self.encryption.is_none()),
+            "Earliest first vote tie-break is supported only by plurality elections \
+            without secret or encrypted ballots"
+        );
+        if let Some(runoff) = &self.runoff {
+            assert!(
+                self.kind == ElectionKind::Plurality && self.seats == 1,
+                "Runoff is supported only by single seat plurality elections"
+            );
+            assert!(
+                (1..100).contains(&runoff.threshold),
+                "Runoff threshold should be between 1 and 99 percent"
+            );
+            assert!(runoff.duration > 0, "Runoff duration should be positive");
+        }
+        match self.kind {
+            ElectionKind::Approval {
+                min_choices,
+                max_choices,
+            } => {
+                let (min, max) = self.choices_limits(min_choices, max_choices);
+                assert!(min <= max, "Min choices should not exceed max choices");
+                assert!(
+                    max <= self.candidates.len(),
+                    "Max choices should not exceed candidates count"
+                );
+            }
+            ElectionKind::Score { max_score } => {
+                assert!(max_score > 0, "Max score should be positive")
+            }
+            ElectionKind::Quadratic { credits } => {
+                assert!(credits > 0, "Voice credits should be positive")
+            }
+            ElectionKind::Cumulative { points } => {
+                assert!(points > 0, "Points budget should be positive")
+            }
+            ElectionKind::MajorityJudgment { ref grades } => {
+                assert!(grades.len() > 1, "More than one grade should be provided");
+                assert!(
+                    grades.len() <= CANDIDATES_LIMIT.into(),
+                    "Maximum {} grades expected",
+                    CANDIDATES_LIMIT
+                );
+            }
+            ElectionKind::Positional {
+                rule: PositionalRule::Custom(ref points),
+            } => {
+                assert!(!points.is_empty(), "Points should be provided");
+                assert!(
+                    points.len() <= self.candidates.len(),
+                    "Points should not exceed candidates count"
+                );
+                assert!(
+                    points.windows(2).all(|pair| pair[0] >= pair[1]),
+                    "Points should not increase with place"
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Single candidate ballots cast in the open by accounts.
+    fn plain_account_ballots(&self) -> bool {
+        self.kind == ElectionKind::Plurality
+            && self.reveal_end.is_none()
+            && self.encryption.is_none()
+            && self.anonymity.is_none()
+            && self.credentials.is_none()
+    }
+
+    /// Mutually exclusive checks of voters' eligibility and weights set for the election.
+    fn gates(&self) -> Vec<&'static str> {
+        [
+            (self.allowlist_root.is_some(), "Merkle allowlist"),
+            (self.token_gate.is_some(), "Token-gated voting"),
+            (self.nft_contract.is_some(), "NFT-gated voting"),
+        ]
+        .iter()
+        .filter_map(|&(set, gate)| set.then_some(gate))
+        .collect()
+    }
+
+    /// Id of the first round. Runoffs share allowlist, vouchers & delegations of their parent.
+    fn first_round_id(&self, id: ElectionId) -> ElectionId {
+        self.parent.unwrap_or(id)
+    }
+
+    fn choices_limits(&self, min_choices: Option<u8>, max_choices: Option<u8>) -> (usize, usize) {
+        (
+            min_choices.map(usize::from).unwrap_or(1),
+            max_choices
+                .map(usize::from)
+                .unwrap_or(self.candidates.len()),
+        )
+    }
+}
+
+/// Voting method of an election. Defines both ballot format and tally rules.
+#[derive(
+    BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, Default, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum ElectionKind {
+    /// Single candidate per ballot, most votes wins.
+    #[default]
+    Plurality,
+    /// Ranked ballots counted in instant-runoff rounds.
+    InstantRunoff,
+    /// Any number of approved candidates per ballot, most approvals wins.
+    ///
+    /// Ballot size limits default to at least one and at most all candidates.
+    Approval {
+        #[serde(default)]
+        min_choices: Option<u8>,
+        #[serde(default)]
+        max_choices: Option<u8>,
+    },
+    /// Every candidate rated from 0 to `max_score` on each ballot, best total score wins.
+    Score { max_score: u8 },
+    /// Voters spend `credits` on candidates, `k` votes for a candidate cost `k²` credits.
+    Quadratic { credits: u32 },
+    /// Ranked ballots compared pairwise, winner found with the Schulze method.
+    /// Supports up to 32 candidates.
+    Schulze,
+    /// Ranked ballots counted with Single Transferable Vote to fill several seats.
+    Stv,
+    /// Ranked ballots award points to candidates by position, most points wins.
+    Positional { rule: PositionalRule },
+    /// Voters distribute up to `points` among candidates, stacking them as they like.
+    Cumulative { points: u32 },
+    /// Every candidate graded on each ballot, best median grade wins.
+    ///
+    /// `grades` are labels ordered from the worst to the best one.
+    MajorityJudgment { grades: Vec<String> },
+}
+
+/// Points awarded for ranking positions in a positional election.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum PositionalRule {
+    /// `n - 1` points for the first place, `n - 2` for the second and so on.
+    Borda,
+    /// `1` point for the first place, `1/2` for the second, `1/3` for the third and so on.
+    /// Supports up to 40 candidates.
+    Dowdall,
+    /// Points for each place, starting from the first one. Should be non-increasing.
+    Custom(Vec<u32>),
+}
+
+impl PositionalRule {
+    /// Points for a place (0 based) out of `candidates`.
+    /// Dowdall points are fractional, so they are stored in [dowdall_scale] units
+    /// to stay exact.
+    fn points(&self, position: usize, candidates: usize) -> u128 {
+        match self {
+            Self::Borda => (candidates - 1 - position) as u128,
+            Self::Dowdall => dowdall_scale(candidates) / (position as u128 + 1),
+            Self::Custom(points) => points.get(position).cloned().map_or(0, u128::from),
+        }
+    }
+}
+
+/// Ranking with the number of ballots cast with it.
+///
+/// Identical ballots are grouped, so finalization reads every distinct ranking once.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct RankingTally {
+    ranking: Vec<CandidateId>,
+    ballots: u128,
+}
+
+/// Accumulated ratings of a candidate in a score election.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+struct ScoreTally {
+    total: u128,
+    ballots: u128,
+}
+
+impl ScoreTally {
+    /// Average score with two decimal places, e.g. `3.67`.
+    fn average(&self) -> String {
+        if self.ballots == 0 {
+            return "0.00".to_string();
+        }
+        let hundredths = (self.total * 200 + self.ballots) / (self.ballots * 2);
+        format!("{}.{:02}", hundredths / 100, hundredths % 100)
+    }
+}
+
+/// Election outcome computed once at finalization.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct ElectionResult {
+    /// Candidates in order of election. Fewer than seats in case of unresolved ties.
+    elected: Vec<CandidateId>,
+    /// Second round created because nobody passed the runoff threshold.
+    runoff: Option<ElectionId>,
+    /// Droop quota of Single Transferable Vote elections.
+    quota: Option<u128>,
+    rounds: Vec<irv::Round>,
+    outcome: Outcome,
+    /// Number of ballots needed for a valid election.
+    required_turnout: Option<u128>,
+    tie_break: Option<TieResolution>,
+}
+
+/// Tie for the last seats resolved with election tie-break policy.
+#[derive(BorshDeserialize, BorshSerialize)]
+struct TieResolution {
+    /// Candidates tied for the seats left.
+    tied: Vec<CandidateId>,
+    /// Tied candidates elected or advanced to the runoff, best first.
+    /// Empty until organization decides, and for ties broken during ranked ballots count,
+    /// see [Round](irv::Round).
+    chosen: Vec<CandidateId>,
+    /// Block random seed of a random tie-break.
+    seed: Option<Vec<u8>>,
+}
+
+/// How an election is resolved.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum Outcome {
+    /// All seats are filled.
+    Elected,
+    /// Second round is needed.
+    Runoff,
+    /// Fewer ballots than quorum, nobody is elected.
+    NoQuorum,
+    /// Winner didn't get the required share of votes, nobody is elected.
+    ThresholdNotMet,
+    /// Tie between candidates for the last seats, they are left unfilled
+    /// unless organization chooses among them, see [TieBreak].
+    Tie,
+}
+
+/// Minimum turnout of an election.
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+enum Quorum {
+    Ballots(u128),
+    /// Percent of accounts on the election allowlist.
+    AllowlistPercent(u8),
+}
+
+/// Share of votes a winner should get.
+#[derive(
+    BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, Default, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum Majority {
+    /// Most votes wins.
+    #[default]
+    Plurality,
+    /// More than half of votes.
+    Simple,
+    /// At least two thirds of votes.
+    TwoThirds,
+    /// At least three quarters of votes.
+    ThreeQuarters,
+}
+
+/// How a tie for the last seats is resolved at finalization.
+#[derive(
+    BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, Default, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum TieBreak {
+    /// Tied seats are left unfilled.
+    #[default]
+    None,
+    /// Tied candidates are ordered by `sha256(seed || candidate_id)`, lowest hash first,
+    /// where `seed` is block random seed captured at finalization.
+    Random,
+    /// Candidate voted for earlier wins. Candidates first voted in the same block
+    /// or not voted at all are ordered as listed.
+    FirstVote,
+    /// Candidate listed earlier wins.
+    CandidateOrder,
+    /// Organization chooses among tied candidates after finalization.
+    Organization,
+}
+
+impl Majority {
+    fn reached(&self, votes: u128, total: u128) -> bool {
+        match self {
+            Majority::Plurality => true,
+            Majority::Simple => votes * 2 > total,
+            Majority::TwoThirds => votes * 3 >= total * 2,
+            Majority::ThreeQuarters => votes * 4 >= total * 3,
         }
     }
 }
@@ -57,6 +970,209 @@ pub struct ElectionInput {
     title: String,
     description: String,
     candidates: Vec<String>,
+    #[serde(default)]
+    kind: ElectionKind,
+    #[serde(default = "default_seats")]
+    seats: u8,
+    #[serde(default)]
+    runoff: Option<RunoffInput>,
+    /// Enables secret ballots: votes are committed until `end` and revealed until `reveal_end`.
+    #[serde(default)]
+    reveal_end: Option<String>,
+    #[serde(default)]
+    results_visibility: ResultsVisibility,
+    /// Show number of ballots cast even while vote counts are hidden.
+    #[serde(default)]
+    show_turnout: bool,
+    /// Enables ballots encrypted for trustees, tallies are decrypted by them after `end`.
+    #[serde(default)]
+    encryption: Option<EncryptionInput>,
+    /// Enables anonymous ballots submitted with membership proofs by any relayer.
+    #[serde(default)]
+    anonymity: Option<AnonymityInput>,
+    /// Enables ballots cast with credentials blind-signed by the organization with this key,
+    /// see [IssuerKeyInput].
+    #[serde(default)]
+    credentials: Option<IssuerKeyInput>,
+    /// Restricts voting to accounts organization adds to the election allowlist before start.
+    #[serde(default)]
+    allowlist: bool,
+    /// Restricts voting to accounts from a Merkle tree, base64 encoded root.
+    ///
+    /// Leaf of a voter is `sha256(0x00 || borsh(account_id, weight))`, where weight is u128
+    /// number of votes the voter casts (`1` for unweighted elections).
+    /// Parent node is `sha256(0x01 || min(a, b) || max(a, b))` of its children `a` & `b`.
+    #[serde(default)]
+    allowlist_root: Option<Base64VecU8>,
+    /// Restricts voting to holders of a NEP-141 token, ballots are weighted by their balances.
+    #[serde(default)]
+    token_gate: Option<TokenGateInput>,
+    /// Restricts voting to holders of NEP-171 tokens from this contract, one vote per token.
+    #[serde(default)]
+    nft_contract: Option<AccountId>,
+    /// Voters should present [Voucher]s signed by the organization.
+    #[serde(default)]
+    vouchers: bool,
+    /// Enables liquid democracy: ballots of voters who didn't vote are counted
+    /// for their delegates' choices at finalization.
+    #[serde(default)]
+    delegation: bool,
+    /// Enables ballots counted toward turnout, but not toward any candidate.
+    #[serde(default)]
+    blank_options: Vec<BlankOption>,
+    /// Minimum turnout, nobody is elected without it. Blank and delegated ballots count too,
+    /// unrevealed secret ballots don't.
+    #[serde(default)]
+    quorum: Option<QuorumInput>,
+    /// Share of votes the winner should get, among votes for candidates and none of the above.
+    #[serde(default)]
+    majority: Majority,
+    /// Resolution of ties, recorded in final results. Ties are left unresolved by default.
+    ///
+    /// Ties during instant runoff and STV counts are broken by candidate order by default.
+    /// All candidates tied for a runoff advance to it, unless policy breaks the tie
+    /// at finalization.
+    #[serde(default)]
+    tie_break: TieBreak,
+}
+
+/// Minimum turnout of an election.
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum QuorumInput {
+    /// Number of ballots.
+    Ballots(String),
+    /// Percent of accounts on the election allowlist, rounded up.
+    AllowlistPercent(u8),
+}
+
+/// Organization's authorization of an account to vote in an election, issued off-chain.
+///
+/// Signature is ed25519 over borsh serialized `(organization_id, election_id, voter_id, expiry)`,
+/// where `election_id` is u128 and `expiry` is u64.
+/// Runoff accepts vouchers issued for its first round.
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Voucher {
+    /// Timestamp in nanoseconds voucher is valid until.
+    expiry: String,
+    /// Base64 encoded ed25519 signature with organization's voucher key.
+    signature: Base64VecU8,
+}
+
+/// Voting by holders of a NEP-141 token.
+///
+/// Balance is queried with `ft_balance_of` at the moment of voting, so the same tokens
+/// could be transferred and voted with again. Use a non-transferable or locked token
+/// if this matters.
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenGateInput {
+    /// Token contract account.
+    token_id: AccountId,
+    /// Minimum balance to vote with, in token's smallest units. `1` by default.
+    #[serde(default)]
+    min_balance: Option<String>,
+    #[serde(default)]
+    weighting: TokenWeighting,
+}
+
+/// Proof of voter's account being in the election Merkle allowlist.
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MerkleProof {
+    /// Base64 encoded sibling hashes from the voter leaf up to the root.
+    siblings: Vec<Base64VecU8>,
+    /// Voter weight from the leaf. `1` by default.
+    #[serde(default)]
+    weight: Option<String>,
+}
+
+/// RSA public key for blind signatures over voter credentials of a single election.
+///
+/// Credential is a random token kept by the voter. Organization signs it with RSA-FDH
+/// without seeing it: signed message is borsh serialized `(organization_id, election_id, token)`
+/// and full domain hash is MGF1 with `sha256` one byte shorter than modulus.
+/// Runoff accepts credentials issued for its first round.
+///
+/// Since signer doesn't see the message, `election_id` in it doesn't bind the credential.
+/// Organization should generate a fresh key for every election and blind-sign
+/// exactly one credential with it for every eligible voter.
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IssuerKeyInput {
+    /// Base64 encoded big-endian modulus, at least 2048 bits.
+    modulus: Base64VecU8,
+    /// Base64 encoded big-endian public exponent.
+    exponent: Base64VecU8,
+}
+
+/// Anonymous voting with Groth16 proofs over BN254 curve.
+///
+/// Membership circuit should have public inputs in the following order:
+/// voters root, nullifier, candidate id and election field element
+/// (`sha256` of borsh serialized `(organization_id, election_id)`, reduced modulo field order).
+/// Field elements are 32 bytes little-endian, arkworks compressed serialization is used.
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AnonymityInput {
+    /// Merkle root of voters identity commitments, base64 encoded field element.
+    voters_root: Base64VecU8,
+    /// Base64 encoded Groth16 verifying key of the membership circuit.
+    verifying_key: Base64VecU8,
+}
+
+/// Threshold ElGamal settings over Ristretto group.
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EncryptionInput {
+    /// Combined public key ballots are encrypted with, base64 encoded compressed point.
+    public_key: Base64VecU8,
+    /// Trustees in order of their key share indices, starting from 1.
+    trustees: Vec<TrusteeInput>,
+    /// Number of trustees needed to decrypt tallies. All of them by default.
+    #[serde(default)]
+    threshold: Option<u8>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TrusteeInput {
+    account_id: AccountId,
+    /// Public key share, base64 encoded compressed point.
+    public_share: Base64VecU8,
+}
+
+/// Plurality ballot encrypted with exponential ElGamal.
+///
+/// Points are base64 encoded compressed Ristretto points, scalars are 32 bytes little-endian.
+/// Proofs are non-interactive with `sha256` Fiat-Shamir challenges,
+/// bound to borsh serialized `(organization_id, election_id, voter_id)`.
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EncryptedBallot {
+    /// Encryption of `1` for the chosen candidate and `0` for others, in candidates order.
+    /// Each one is `r·G || r·H + m·G`.
+    ciphertexts: Vec<Base64VecU8>,
+    /// Proof of every ciphertext encrypting either `0` or `1`: `c0 || s0 || c1 || s1`.
+    proofs: Vec<Base64VecU8>,
+    /// Proof of ciphertexts sum encrypting `1`: `c || s`.
+    sum_proof: Base64VecU8,
+}
+
+/// Automatic second round between the top two candidates.
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RunoffInput {
+    /// Percent of votes to exceed for a first round win. 50 by default.
+    #[serde(default)]
+    threshold: Option<u8>,
+    /// Runoff duration in nanoseconds.
+    duration: String,
+}
+
+fn default_seats() -> u8 {
+    1
 }
 
 /// Election view for clients.
@@ -68,55 +1184,360 @@ pub struct ElectionView {
     title: String,
     description: String,
     candidates: Vec<Candidate>,
+    kind: ElectionKind,
+    seats: u8,
+    /// Election this one is a runoff of.
+    parent_id: Option<String>,
+    /// Runoff created for this election at finalization.
+    runoff_id: Option<String>,
+    reveal_end: Option<String>,
+    /// Number of ballots cast. Hidden together with vote counts unless organization opted in.
+    turnout: Option<String>,
+    results_visibility: ResultsVisibility,
+    encryption: Option<EncryptionView>,
+    /// Merkle root of voters identity commitments of an election with anonymous ballots.
+    voters_root: Option<Base64VecU8>,
+    /// Key of credentials blind-signed by the organization ballots are cast with.
+    credentials: Option<IssuerKeyInput>,
+    /// Number of accounts allowed to vote in an election restricted to an allowlist.
+    allowlist_size: Option<String>,
+    /// Merkle root of eligible voters.
+    allowlist_root: Option<Base64VecU8>,
+    token_gate: Option<TokenGateInput>,
+    /// NEP-171 contract of NFT passes.
+    nft_contract: Option<AccountId>,
+    /// Voters present vouchers signed by the organization.
+    vouchers: bool,
+    /// Voters could delegate their ballots.
+    delegation: bool,
+    blank_options: Vec<BlankOption>,
+    /// Hidden together with vote counts. Present only if election accepts abstentions.
+    abstentions: Option<String>,
+    /// Votes for none of the candidates. Hidden together with vote counts.
+    /// Present only if election accepts such ballots.
+    nota_votes: Option<String>,
+    quorum: Option<QuorumInput>,
+    majority: Majority,
+    tie_break: TieBreak,
+}
+
+/// Encryption settings of an election with encrypted ballots.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EncryptionView {
+    public_key: Base64VecU8,
+    trustees: Vec<TrusteeView>,
+    threshold: u8,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TrusteeView {
+    account_id: AccountId,
+    public_share: Base64VecU8,
+    /// Trustee has submitted partial decryption of the tallies.
+    decrypted: bool,
 }
 
+/// Secret ballot not revealed yet.
 #[derive(Serialize)]
 #[serde(crate = "near_sdk::serde")]
+pub struct CommitmentView {
+    voter: VoterId,
+    commitment: Base64VecU8,
+}
+
+#[derive(Serialize, Default)]
+#[serde(crate = "near_sdk::serde")]
 struct Candidate {
     name: String,
-    votes: String,
+    /// Hidden while election's results visibility policy doesn't allow to show them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    votes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_score: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    average_score: Option<String>,
+    /// Number of ballots giving each grade of a majority judgment election, worst grade first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    histogram: Option<Vec<String>>,
 }
 
-#[derive(BorshSerialize, BorshStorageKey)]
-enum StorageKeys {
-    Organizations,
-    Elections,
-    Results,
-    Voters,
+/// Final election results view for clients.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ResultView {
+    /// First elected candidate.
+    winner: Option<CandidateId>,
+    /// All elected candidates in order of election.
+    elected: Vec<CandidateId>,
+    /// Second round created because nobody passed the threshold.
+    runoff_id: Option<String>,
+    /// Median grade label of every candidate in a majority judgment election.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    median_grades: Option<Vec<String>>,
+    quota: Option<String>,
+    rounds: Vec<RoundView>,
+    outcome: Outcome,
+    /// Number of ballots counted. Unrevealed secret ballots are not.
+    turnout: String,
+    /// Number of ballots needed for a valid election.
+    required_turnout: Option<String>,
+    tie_break: Option<TieBreakView>,
 }
 
-#[near_bindgen]
-impl Elections {
-    /// Contract init function. Could be called only once.
-    ///
-    /// Sets the caller as contract owner.
-    #[init]
-    pub fn new() -> Self {
-        Self {
-            owner_id: env::predecessor_account_id(),
-            organizations: LookupMap::new(StorageKeys::Organizations),
-            elections: LookupMap::new(StorageKeys::Elections),
+/// Resolution of a tie for the last seats.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TieBreakView {
+    policy: TieBreak,
+    /// Candidates tied for the seats left.
+    tied: Vec<CandidateId>,
+    /// Tied candidates elected or advanced to the runoff, best first.
+    /// Empty until organization decides, and for ties broken during ranked ballots count.
+    chosen: Vec<CandidateId>,
+    /// Base64 encoded block random seed of a random tie-break.
+    seed: Option<Base64VecU8>,
+}
+
+/// Pairwise comparison of candidates in a Schulze election.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PairwiseView {
+    /// `matrix[a][b]` - number of voters preferring candidate `a` over candidate `b`.
+    matrix: Vec<Vec<String>>,
+    /// `strongest_paths[a][b]` - strength of the strongest path from `a` to `b`.
+    strongest_paths: Vec<Vec<String>>,
+    condorcet_winner: Option<CandidateId>,
+    /// Candidates not beaten through the strongest paths. More than one in case of a tie.
+    schulze_winners: Vec<CandidateId>,
+}
+
+/// Ranked ballots counting round view.
+///
+/// Votes of Single Transferable Vote elections are fractional, e.g. `2.500000`.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct RoundView {
+    tallies: Vec<CandidateTally>,
+    exhausted: String,
+    elected: Vec<CandidateId>,
+    eliminated: Option<CandidateId>,
+    /// Candidates tied for the election or elimination, broken with election tie-break policy.
+    tied: Vec<CandidateId>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct CandidateTally {
+    candidate: CandidateId,
+    votes: String,
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+enum StorageKeys {
+    Organizations,
+    /// Elections stored before they got settings, moved by
+    /// [migrate_elections](Elections::migrate_elections).
+    Elections,
+    Results,
+    Voters,
+    BallotsCount,
+    Rankings,
+    ElectionResults,
+    Scores,
+    Pairwise,
+    Grades,
+    Commitments,
+    Committers,
+    EncryptedTallies,
+    DecryptionShares,
+    Allowlists,
+    AllowlistPositions,
+    AllowlistSizes,
+    VoucherKeys,
+    Delegations,
+    Delegators,
+    Choices,
+    BlankVotes,
+    FirstVotes,
+    DelegationChanges,
+    DelegatorsCounts,
+    CountedDelegators,
+    RankingIds,
+    RankingsCounts,
+    DecryptedTallies,
+    CandidateBallots,
+    ElectionsV2,
+    BallotVoters,
+    RevealedCounts,
+}
+
+#[near_bindgen]
+#[allow(clippy::ptr_arg)]
+impl Elections {
+    /// Contract init function. Could be called only once.
+    ///
+    /// Sets the caller as contract owner.
+    #[init]
+    pub fn new() -> Self {
+        Self {
+            owner_id: env::predecessor_account_id(),
+            organizations: LookupMap::new(StorageKeys::Organizations),
+            elections: LookupMap::new(StorageKeys::ElectionsV2),
             votes: LookupMap::new(StorageKeys::Results),
             voters: LookupSet::new(StorageKeys::Voters),
+            ballot_voters: LookupSet::new(StorageKeys::BallotVoters),
+            ballots_count: LookupMap::new(StorageKeys::BallotsCount),
+            rankings: LookupMap::new(StorageKeys::Rankings),
+            ranking_ids: LookupMap::new(StorageKeys::RankingIds),
+            rankings_counts: LookupMap::new(StorageKeys::RankingsCounts),
+            results: LookupMap::new(StorageKeys::ElectionResults),
+            scores: LookupMap::new(StorageKeys::Scores),
+            pairwise: LookupMap::new(StorageKeys::Pairwise),
+            grades: LookupMap::new(StorageKeys::Grades),
+            commitments: LookupMap::new(StorageKeys::Commitments),
+            committers: LookupMap::new(StorageKeys::Committers),
+            encrypted_tallies: LookupMap::new(StorageKeys::EncryptedTallies),
+            decryption_shares: LookupMap::new(StorageKeys::DecryptionShares),
+            decrypted_tallies: LookupSet::new(StorageKeys::DecryptedTallies),
+            allowlists: LookupMap::new(StorageKeys::Allowlists),
+            allowlist_positions: LookupMap::new(StorageKeys::AllowlistPositions),
+            allowlist_sizes: LookupMap::new(StorageKeys::AllowlistSizes),
+            voucher_keys: LookupMap::new(StorageKeys::VoucherKeys),
+            delegations: LookupMap::new(StorageKeys::Delegations),
+            delegation_changes: LookupMap::new(StorageKeys::DelegationChanges),
+            delegators: LookupMap::new(StorageKeys::Delegators),
+            delegators_counts: LookupMap::new(StorageKeys::DelegatorsCounts),
+            counted_delegators: LookupMap::new(StorageKeys::CountedDelegators),
+            choices: LookupMap::new(StorageKeys::Choices),
+            blank_votes: LookupMap::new(StorageKeys::BlankVotes),
+            first_votes: LookupMap::new(StorageKeys::FirstVotes),
+            candidate_ballots: LookupMap::new(StorageKeys::CandidateBallots),
+            revealed_counts: LookupMap::new(StorageKeys::RevealedCounts),
+        }
+    }
+
+    /// Upgrades contract state stored before elections got settings.
+    ///
+    /// Organizations, votes and voters are kept as they are.
+    /// Elections should be moved afterwards, see [migrate_elections](#method.migrate_elections).
+    ///
+    /// # Panics
+    ///
+    /// * Only contract account is allowed to call this function.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let legacy: LegacyElections = env::state_read().expect("Contract state not found");
+        Self {
+            owner_id: legacy.owner_id,
+            organizations: legacy.organizations,
+            votes: legacy.votes,
+            voters: legacy.voters,
+            ..Self::new()
+        }
+    }
+
+    /// Moves elections stored before elections got settings into plurality ones.
+    ///
+    /// Ballots already cast are counted toward their turnout.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_ids` - String ids of elections to move, already moved ones are skipped
+    ///
+    /// # Panics
+    ///
+    /// * Only owner is allowed to call this function.
+    /// * `election_ids` should be parsed as u128.
+    pub fn migrate_elections(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_ids: Vec<String>,
+    ) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only owner can migrate elections"
+        );
+        let mut legacy: LookupMap<(OrganizationId, ElectionId), LegacyElection> =
+            LookupMap::new(StorageKeys::Elections);
+        for election_id in election_ids {
+            let key = (organization_id.clone(), election_id.parse().unwrap());
+            let election = match legacy.remove(&key) {
+                Some(election) => Election::from_legacy(election),
+                None => continue,
+            };
+            let turnout = (0..election.candidates.len())
+                .map(|i| {
+                    self.votes
+                        .get(&(key.0.clone(), key.1, i.try_into().unwrap()))
+                        .unwrap_or(0)
+                })
+                .sum();
+            self.ballots_count.insert(&key, &turnout);
+            self.elections.insert(&key, &election);
         }
     }
 
     /// Register account as an organization.
     ///
+    /// Registering an organization again updates its voucher key, keeping its elections.
+    ///
     /// # Arguments
     ///
     /// * `account` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `voucher_key` - optional base64 encoded ed25519 public key
+    ///   organization signs [Voucher](struct.Voucher.html)s with
     ///
     /// # Panics
     ///
     /// * Only owner is allowed to call this function.
-    pub fn register_organization(&mut self, account: &OrganizationId) {
+    /// * Voucher key should be a valid ed25519 public key.
+    pub fn register_organization(
+        &mut self,
+        account: &OrganizationId,
+        voucher_key: Option<Base64VecU8>,
+    ) {
         assert_eq!(
             env::predecessor_account_id(),
             self.owner_id,
             "Only owner can register new organizations"
         );
-        self.organizations.insert(account, &0);
+        if let Some(key) = voucher_key {
+            assert!(voucher::valid_key(&key.0), "Invalid voucher key");
+            self.voucher_keys.insert(account, &to_key(&key));
+        }
+        if !self.organizations.contains_key(account) {
+            self.organizations.insert(account, &0);
+        }
+    }
+
+    /// Set or rotate caller organization's voucher key.
+    ///
+    /// Vouchers signed with the previous key are not accepted anymore.
+    ///
+    /// # Arguments
+    ///
+    /// * `voucher_key` - base64 encoded ed25519 public key
+    ///   organization signs [Voucher](struct.Voucher.html)s with
+    ///
+    /// # Panics
+    ///
+    /// * Only registered organization is allowed to call this function.
+    /// * Voucher key should be a valid ed25519 public key.
+    pub fn set_voucher_key(&mut self, voucher_key: Base64VecU8) {
+        let organization_id = env::predecessor_account_id();
+        assert!(
+            self.organizations.contains_key(&organization_id),
+            "{}",
+            NOT_REGISTERED_ERROR
+        );
+        assert!(voucher::valid_key(&voucher_key.0), "Invalid voucher key");
+        self.voucher_keys
+            .insert(&organization_id, &to_key(&voucher_key));
     }
 
     /// Create new election.
@@ -129,9 +1550,52 @@ impl Elections {
     ///
     /// * Function is a paid one. Expects exactly **1 NEAR** deposit.
     /// * Only registered organization is allowed to call this function.
-    /// * Candidates array length should be between 2 and 256 elements.
+    /// * Candidates array length should be between 2 and 256 elements,
+    ///   up to 32 elements for `schulze` and 40 for Dowdall `positional` elections.
     /// * Start and end dates are validated based on block timestamp.
     ///   They both should be in the future and end should be after start.
+    /// * Seats should be fewer than candidates.
+    ///   Several seats are supported by every election kind except `instant_runoff` & `schulze`.
+    /// * Approval ballot limits should be consistent with candidates count.
+    /// * Score scale should be positive.
+    /// * Quadratic voice credits & cumulative points budget should be positive.
+    /// * Runoff is supported only by single seat plurality elections.
+    ///   Its threshold should be between 1 and 99 percent and duration should be positive.
+    /// * Secret ballots are supported only by plurality elections
+    ///   and reveal end should be after election end.
+    /// * Majority judgment should have between 2 and 256 grades.
+    /// * Custom positional points should be non-increasing
+    ///   and provided for at most as many places as there are candidates.
+    /// * Encrypted ballots are supported only by plurality elections without secret ballots.
+    ///   Trustees should be distinct, threshold should be between 1 and their count
+    ///   and public key shares should be consistent with the combined public key.
+    /// * Anonymous ballots are supported only by plurality elections
+    ///   without secret or encrypted ballots.
+    ///   Voters root and verifying key should be valid.
+    /// * Credentials are supported only by plurality elections
+    ///   without secret, encrypted or anonymous ballots.
+    ///   Issuer key should be a valid RSA public key with at least 2048 bits modulus.
+    /// * Allowlist is supported only by elections with ballots cast by accounts.
+    /// * Merkle allowlist is supported only by plurality elections with plain ballots
+    ///   and can't be combined with an allowlist.
+    /// * Token-gated voting is supported only by plurality elections with plain ballots
+    ///   cast by accounts and can't be combined with a Merkle allowlist.
+    ///   Minimum token balance should be positive.
+    /// * NFT-gated voting is supported only by plurality elections with plain ballots
+    ///   cast by accounts and can't be combined with a Merkle allowlist or token gate.
+    /// * Vouchers are supported only by plurality elections with plain ballots cast by accounts.
+    ///   Organization should have a voucher key.
+    /// * Delegation is supported only by plurality elections with plain unweighted ballots
+    ///   cast by accounts without vouchers.
+    /// * Blank ballots are supported only by plurality elections with plain ballots
+    ///   cast by accounts. Every blank option should be listed once.
+    /// * Quorum should be positive. Allowlist quorum is supported only by elections
+    ///   with allowlist and should be between 1 and 100 percent.
+    /// * Majority threshold is supported only by single seat plurality elections without runoff.
+    /// * Only random and candidate order tie-breaks are supported by instant runoff
+    ///   and STV elections.
+    /// * Earliest first vote tie-break is supported only by plurality elections
+    ///   without secret or encrypted ballots.
     #[payable]
     pub fn create_election(&mut self, input: &ElectionInput) -> String {
         let election = Election::new(input);
@@ -149,6 +1613,7 @@ impl Elections {
             "Start should be in the future"
         );
         assert!(election.start < election.end, "Start should be before end");
+        election.assert_valid_settings();
         assert!(
             env::attached_deposit() == to_yocto(CREATE_ELECTION_COST),
             "Create election is paid function. Expects to receive exactly {} NEAR",
@@ -160,11 +1625,142 @@ impl Elections {
             .organizations
             .get(&organization_id)
             .expect(NOT_REGISTERED_ERROR);
+        assert!(
+            !election.vouchers || self.voucher_keys.contains_key(&organization_id),
+            "Organization has no voucher key"
+        );
         self.organizations.insert(&organization_id, &(id + 1));
         self.elections.insert(&(organization_id, id), &election);
         id.to_string()
     }
 
+    /// Make results of an election with `after_publish` visibility public.
+    ///
+    /// # Arguments
+    ///
+    /// * `election_id` - String id of caller organization's election
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` can not be parsed as u128
+    /// * Election not found.
+    /// * Election should have `after_publish` results visibility.
+    /// * Election (including its reveal phase) should be ended.
+    pub fn publish_results(&mut self, election_id: &String) {
+        let key = (env::predecessor_account_id(), election_id.parse().unwrap());
+        let mut election = self.elections.get(&key).expect(NOT_FOUND_ERROR);
+        assert!(
+            election.results_visibility == ResultsVisibility::AfterPublish,
+            "Election results are not published manually"
+        );
+        assert!(
+            election.counting_end() < env::block_timestamp(),
+            "Election not ended yet"
+        );
+        election.published = true;
+        self.elections.insert(&key, &election);
+    }
+
+    /// Allow accounts to vote in caller organization's election restricted to an allowlist.
+    ///
+    /// Caller pays for the storage used by new entries, excess of the deposit is refunded.
+    ///
+    /// # Arguments
+    ///
+    /// * `election_id` - String id of caller organization's election
+    /// * `account_ids` - accounts to add, already allowed ones are skipped
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` can not be parsed as u128
+    /// * Election not found.
+    /// * Election should be restricted to an allowlist.
+    /// * Election shouldn't be started yet.
+    /// * Attached deposit should cover the storage used.
+    #[payable]
+    pub fn add_to_allowlist(&mut self, election_id: &String, account_ids: Vec<VoterId>) {
+        let initial_storage = env::storage_usage();
+        let key = (env::predecessor_account_id(), election_id.parse().unwrap());
+        self.assert_allowlist_editable(&key);
+        let mut size = self.allowlist_sizes.get(&key).unwrap_or(0);
+        for account_id in account_ids {
+            let position_key = &(key.0.clone(), key.1, account_id);
+            if self.allowlist_positions.contains_key(position_key) {
+                continue;
+            }
+            self.allowlist_positions.insert(position_key, &size);
+            self.allowlists
+                .insert(&(key.0.clone(), key.1, size), &position_key.2);
+            size += 1;
+        }
+        self.allowlist_sizes.insert(&key, &size);
+
+        let cost = env::storage_byte_cost()
+            * u128::from(env::storage_usage().saturating_sub(initial_storage));
+        assert!(
+            env::attached_deposit() >= cost,
+            "Attached deposit should cover storage. Expects to receive at least {} yoctoNEAR",
+            cost
+        );
+        let refund = env::attached_deposit() - cost;
+        if refund > 0 {
+            Promise::new(key.0).transfer(refund);
+        }
+    }
+
+    /// Remove accounts from caller organization's election allowlist.
+    ///
+    /// # Arguments
+    ///
+    /// * `election_id` - String id of caller organization's election
+    /// * `account_ids` - accounts to remove, not allowed ones are skipped
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` can not be parsed as u128
+    /// * Election not found.
+    /// * Election should be restricted to an allowlist.
+    /// * Election shouldn't be started yet.
+    pub fn remove_from_allowlist(&mut self, election_id: &String, account_ids: Vec<VoterId>) {
+        let key = (env::predecessor_account_id(), election_id.parse().unwrap());
+        self.assert_allowlist_editable(&key);
+        let mut size = self.allowlist_sizes.get(&key).unwrap_or(0);
+        for account_id in account_ids {
+            let position =
+                match self
+                    .allowlist_positions
+                    .remove(&(key.0.clone(), key.1, account_id))
+                {
+                    Some(position) => position,
+                    None => continue,
+                };
+            // Last entry takes place of the removed one to keep the list dense.
+            size -= 1;
+            let last = self
+                .allowlists
+                .remove(&(key.0.clone(), key.1, size))
+                .unwrap();
+            if position != size {
+                self.allowlist_positions
+                    .insert(&(key.0.clone(), key.1, last.clone()), &position);
+                self.allowlists
+                    .insert(&(key.0.clone(), key.1, position), &last);
+            }
+        }
+        self.allowlist_sizes.insert(&key, &size);
+    }
+
+    /// Returns ed25519 public key organization signs vouchers with.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    pub fn get_voucher_key(&self, organization_id: &OrganizationId) -> Option<Base64VecU8> {
+        self.voucher_keys
+            .get(organization_id)
+            .map(|key| key.to_vec().into())
+    }
+
     /// Returns number of elections for an organization.
     ///
     /// # Arguments
@@ -197,40 +1793,120 @@ impl Elections {
         organization_id: &OrganizationId,
         election_id: &String,
     ) -> ElectionView {
+        let election_id_parsed = election_id.parse::<u128>().unwrap();
         let election = self
             .elections
-            .get(&(
-                organization_id.clone(),
-                election_id.parse::<u128>().unwrap(),
-            ))
-            .expect("Election not found");
+            .get(&(organization_id.clone(), election_id_parsed))
+            .expect(NOT_FOUND_ERROR);
+        let visible =
+            self.results_visible(&(organization_id.clone(), election_id_parsed), &election);
+        let blank_votes = |option| {
+            (visible && election.blank_options.contains(&option)).then(|| {
+                self.blank_votes
+                    .get(&(organization_id.clone(), election_id_parsed, option))
+                    .unwrap_or(0)
+                    .to_string()
+            })
+        };
+        let abstentions = blank_votes(BlankOption::Abstain);
+        let nota_votes = blank_votes(BlankOption::Nota);
+        let turnout = (visible || election.show_turnout).then(|| {
+            self.counted_ballots(&(organization_id.clone(), election_id_parsed), &election)
+                .to_string()
+        });
+        let allowlist_size = election.allowlist.then(|| {
+            self.allowlist_sizes
+                .get(&(
+                    organization_id.clone(),
+                    election.first_round_id(election_id_parsed),
+                ))
+                .unwrap_or(0)
+                .to_string()
+        });
 
         ElectionView {
             start: election.start.to_string(),
             end: election.end.to_string(),
-            title: election.title,
-            description: election.description,
+            title: election.title.clone(),
+            description: election.description.clone(),
             candidates: election
                 .candidates
                 .iter()
                 .enumerate()
-                .map(|(i, candidate)| Candidate {
-                    name: candidate.clone(),
-                    votes: self
-                        .votes
-                        .get(&(
-                            organization_id.clone(),
-                            election_id.parse().unwrap(),
-                            i.try_into().unwrap(),
-                        ))
-                        .unwrap_or(0)
-                        .to_string(),
+                .map(|(i, name)| {
+                    if visible {
+                        self.candidate_view(
+                            &(organization_id.clone(), election_id_parsed),
+                            &election,
+                            i,
+                            name,
+                        )
+                    } else {
+                        Candidate {
+                            name: name.clone(),
+                            ..Default::default()
+                        }
+                    }
                 })
                 .collect(),
+            kind: election.kind,
+            seats: election.seats,
+            parent_id: election.parent.map(|id| id.to_string()),
+            runoff_id: self
+                .results
+                .get(&(organization_id.clone(), election_id_parsed))
+                .and_then(|result| result.runoff)
+                .map(|id| id.to_string()),
+            reveal_end: election.reveal_end.map(|end| end.to_string()),
+            turnout,
+            results_visibility: election.results_visibility,
+            encryption: election.encryption.map(|encryption| EncryptionView {
+                public_key: encryption.public_key.to_vec().into(),
+                trustees: encryption
+                    .trustees
+                    .into_iter()
+                    .map(|(account_id, public_share)| TrusteeView {
+                        decrypted: self.decryption_shares.contains_key(&(
+                            organization_id.clone(),
+                            election_id_parsed,
+                            account_id.clone(),
+                        )),
+                        account_id,
+                        public_share: public_share.to_vec().into(),
+                    })
+                    .collect(),
+                threshold: encryption.threshold,
+            }),
+            voters_root: election
+                .anonymity
+                .map(|anonymity| anonymity.voters_root.to_vec().into()),
+            credentials: election.credentials.map(|key| IssuerKeyInput {
+                modulus: key.modulus.into(),
+                exponent: key.exponent.into(),
+            }),
+            allowlist_size,
+            allowlist_root: election.allowlist_root.map(|root| root.to_vec().into()),
+            token_gate: election.token_gate.map(|gate| TokenGateInput {
+                token_id: gate.token_id,
+                min_balance: Some(gate.min_balance.to_string()),
+                weighting: gate.weighting,
+            }),
+            nft_contract: election.nft_contract,
+            vouchers: election.vouchers,
+            delegation: election.delegation,
+            abstentions,
+            nota_votes,
+            blank_options: election.blank_options,
+            quorum: election.quorum.map(|quorum| match quorum {
+                Quorum::Ballots(ballots) => QuorumInput::Ballots(ballots.to_string()),
+                Quorum::AllowlistPercent(percent) => QuorumInput::AllowlistPercent(percent),
+            }),
+            majority: election.majority,
+            tie_break: election.tie_break,
         }
     }
 
-    /// Checks if caller has already voted.
+    /// Checks if caller has already voted, either directly or through delegates.
     ///
     /// # Arguments
     ///
@@ -240,12 +1916,130 @@ impl Elections {
     /// # Panics
     ///
     /// * `election_id` can not be parsed as u128
-    pub fn have_voted(&mut self, organization_id: &OrganizationId, election_id: &String) -> bool {
-        self.voters.contains(&(
+    pub fn have_voted(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+    ) -> VoteStatus {
+        let election_id_parsed = election_id.parse().unwrap();
+        let voter_id = env::predecessor_account_id();
+        if self.voters.contains(&(
             organization_id.clone(),
-            election_id.parse().unwrap(),
-            env::predecessor_account_id(),
-        ))
+            election_id_parsed,
+            voter_id.clone(),
+        )) {
+            return VoteStatus::Direct;
+        }
+        let delegated = self
+            .elections
+            .get(&(organization_id.clone(), election_id_parsed))
+            .filter(|election| election.delegation)
+            .is_some_and(|election| {
+                self.allowed(organization_id, election_id_parsed, &election, &voter_id)
+                    && self
+                        .delegated_choice(organization_id, election_id_parsed, &election, &voter_id)
+                        .is_some()
+            });
+        if delegated {
+            VoteStatus::Delegated
+        } else {
+            VoteStatus::NotVoted
+        }
+    }
+
+    /// Delegate caller's ballot to another account.
+    ///
+    /// Delegations are transitive: ballot goes to the choice of the first account
+    /// in the chain of delegates who voted directly. Chains forming a cycle
+    /// or longer than 10 delegates are not counted.
+    /// Caller's direct ballot overrides the delegation.
+    /// Delegation for an election takes precedence over organization-wide one
+    /// and applies to its runoff as well.
+    /// Elections are counted with delegations active at their end, later changes don't affect them.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - optional String id of an election, every organization's election if absent
+    /// * `delegate_id` - [AccountId](../near_sdk/struct.AccountId.html) of a delegate
+    ///
+    /// # Panics
+    ///
+    /// * Organization should be registered.
+    /// * Caller shouldn't delegate to themselves.
+    /// * Election should support delegation and shouldn't be ended yet.
+    pub fn delegate(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: Option<String>,
+        delegate_id: VoterId,
+    ) {
+        let voter_id = env::predecessor_account_id();
+        assert!(delegate_id != voter_id, "Can't delegate to yourself");
+        let scope = self.delegation_scope(organization_id, election_id);
+        let key = (organization_id.clone(), scope, voter_id.clone());
+        if !self.delegation_changes.contains_key(&key) {
+            let scope_key = &(organization_id.clone(), scope);
+            let count = self.delegators_counts.get(scope_key).unwrap_or(0);
+            self.delegators
+                .insert(&(organization_id.clone(), scope, count), &voter_id);
+            self.delegators_counts.insert(scope_key, &(count + 1));
+        }
+        self.record_delegation(&key, Some(delegate_id));
+    }
+
+    /// Revoke caller's delegation.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - optional String id of an election, organization-wide delegation if absent
+    ///
+    /// # Panics
+    ///
+    /// * Organization should be registered.
+    /// * Election should support delegation and shouldn't be ended yet.
+    /// * Caller should have a delegation.
+    pub fn undelegate(&mut self, organization_id: &OrganizationId, election_id: Option<String>) {
+        let voter_id = env::predecessor_account_id();
+        let scope = self.delegation_scope(organization_id, election_id);
+        let key = (organization_id.clone(), scope, voter_id);
+        assert!(
+            self.delegation_at(&key, u64::MAX).is_some(),
+            "Delegation not found"
+        );
+        self.record_delegation(&key, None);
+    }
+
+    /// Returns delegate of an account.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - optional String id of an election, organization-wide delegation if absent
+    /// * `account_id` - [AccountId](../near_sdk/struct.AccountId.html) of a delegator
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` can not be parsed as u128
+    /// * Election not found.
+    pub fn get_delegate(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: Option<String>,
+        account_id: &VoterId,
+    ) -> Option<VoterId> {
+        let scope = election_id.map(|id| {
+            let election_id = id.parse().unwrap();
+            self.elections
+                .get(&(organization_id.clone(), election_id))
+                .expect(NOT_FOUND_ERROR)
+                .first_round_id(election_id)
+        });
+        self.delegation_at(
+            &(organization_id.clone(), scope, account_id.clone()),
+            u64::MAX,
+        )
     }
 
     /// Vote in some election.
@@ -255,368 +2049,6687 @@ impl Elections {
     /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
     /// * `election_id` - String id
     /// * `candidate_id` - u8 id
+    /// * `proof` - [MerkleProof](struct.MerkleProof.html) of caller's eligibility,
+    ///   required only by elections with Merkle allowlist. Ballot counts as many votes as weight.
+    /// * `voucher` - [Voucher](struct.Voucher.html) issued to caller,
+    ///   required only by elections with vouchers
     ///
     /// # Panics
     ///
     /// * `election_id` should be parsed as u128.
     /// * `organization_id` & `election_id` & `candidate_id` should be a valid combination.
+    /// * Election should be of `plurality` kind with plain ballots cast by accounts.
+    /// * Merkle proof should be valid if election has Merkle allowlist.
+    /// * Voucher should be valid and not expired if election requires vouchers.
     /// * Current date should be between start and end dates of the election.
-    /// * User shouldn't try to vote more than once.
+    ///
+    /// Voting again replaces the previous choice, so only the last ballot counts.
     pub fn vote(
         &mut self,
         organization_id: &OrganizationId,
         election_id: &String,
         candidate_id: u8,
+        proof: Option<MerkleProof>,
+        voucher: Option<Voucher>,
     ) {
         let election_id_parsed = election_id.parse().unwrap();
-        let election = self
-            .elections
-            .get(&(organization_id.clone(), election_id_parsed))
-            .unwrap();
-        assert!(
-            election.start < env::block_timestamp(),
-            "Election not started yet"
+        let election = self.open_election(organization_id, election_id_parsed);
+        assert_candidate(&election, candidate_id);
+        self.cast_plain_ballot(
+            organization_id,
+            election_id_parsed,
+            &election,
+            BallotOption::Candidate(candidate_id),
+            proof,
+            voucher,
         );
+    }
+
+    /// Abstain or vote for none of the candidates.
+    ///
+    /// Blank ballot counts toward turnout, but not toward any candidate.
+    /// Could be changed or retracted the same way as ballots cast with [vote](#method.vote).
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `option` - [BlankOption](enum.BlankOption.html) enabled by the election
+    /// * `proof` - same as for [vote](#method.vote)
+    /// * `voucher` - same as for [vote](#method.vote)
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election should accept the blank option.
+    /// * Same as for [vote](#method.vote).
+    pub fn vote_blank(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        option: BlankOption,
+        proof: Option<MerkleProof>,
+        voucher: Option<Voucher>,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self.open_election(organization_id, election_id_parsed);
         assert!(
-            election.end > env::block_timestamp(),
-            "Election already ended"
+            election.blank_options.contains(&option),
+            "Election doesn't accept {:?} ballots",
+            option
         );
-        let voter_key = &(
-            organization_id.clone(),
+        self.cast_plain_ballot(
+            organization_id,
             election_id_parsed,
-            env::predecessor_account_id(),
+            &election,
+            BallotOption::Blank(option),
+            proof,
+            voucher,
         );
-        assert!(!self.voters.contains(voter_key), "User already voted");
-
-        let candidate_key = &(organization_id.clone(), election_id_parsed, candidate_id);
-        let votes = self.votes.get(candidate_key).unwrap_or(0);
-        self.votes.insert(candidate_key, &(votes + 1));
-        self.voters.insert(voter_key);
     }
-}
-
-fn to_yocto(n: u128) -> u128 {
-    n * (10 as u128).pow(24)
-}
 
-#[cfg(test)]
-mod tests {
-    use std::convert::TryInto;
+    /// Retract caller's ballot cast with [vote](#method.vote).
+    ///
+    /// Caller could vote again afterwards. Delegation of the caller, if any, applies again.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Current date should be between start and end dates of the election.
+    /// * Caller should have a ballot to retract.
+    pub fn retract_vote(&mut self, organization_id: &OrganizationId, election_id: &String) {
+        let election_id_parsed = election_id.parse().unwrap();
+        self.open_election(organization_id, election_id_parsed);
+        let voter_id = env::predecessor_account_id();
+        let choice = self
+            .choices
+            .remove(&(
+                organization_id.clone(),
+                election_id_parsed,
+                voter_id.clone(),
+            ))
+            .expect("No ballot to retract");
+        let votes = self.choice_votes(organization_id, election_id_parsed, choice.option);
+        self.set_choice_votes(
+            organization_id,
+            election_id_parsed,
+            choice.option,
+            votes - choice.weight,
+        );
+        self.voters
+            .remove(&(organization_id.clone(), election_id_parsed, voter_id));
+        if let (BallotOption::Candidate(candidate_id), Some(position)) =
+            (choice.option, choice.listed)
+        {
+            self.unlist_first_vote(organization_id, election_id_parsed, candidate_id, position);
+        }
+        // Ballot ids are not used by elections with plain ballots, so the count could go down.
+        let election_key = &(organization_id.clone(), election_id_parsed);
+        let count = self.ballots_count.get(election_key).unwrap_or(0);
+        self.ballots_count.insert(election_key, &(count - 1));
+    }
 
-    use super::*;
-    use chrono::{DateTime, Duration, Utc};
+    /// Vote in a token-gated election.
+    ///
+    /// Queries caller's balance from the token contract, ballot is counted in the callback.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `candidate_id` - u8 id
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * `organization_id` & `election_id` & `candidate_id` should be a valid combination.
+    /// * Election should be token-gated.
+    /// * Current date should be between start and end dates of the election.
+    /// * Caller should be on the election allowlist if election has one.
+    /// * User shouldn't try to vote more than once.
+    pub fn vote_with_tokens(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        candidate_id: u8,
+    ) -> Promise {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self.open_election(organization_id, election_id_parsed);
+        let token_gate = election
+            .token_gate
+            .as_ref()
+            .expect("Election isn't token-gated");
+        assert_candidate(&election, candidate_id);
+        let voter_id = env::predecessor_account_id();
+        self.assert_allowed(organization_id, election_id_parsed, &election, &voter_id);
+        assert!(
+            !self.voters.contains(&(
+                organization_id.clone(),
+                election_id_parsed,
+                voter_id.clone(),
+            )),
+            "User already voted"
+        );
+
+        ext_ft::ft_balance_of(
+            voter_id.clone(),
+            token_gate.token_id.clone(),
+            0,
+            FT_BALANCE_OF_GAS,
+        )
+        .then(ext_self::on_token_balance(
+            organization_id.clone(),
+            election_id.clone(),
+            candidate_id,
+            voter_id,
+            env::current_account_id(),
+            0,
+            VOTE_CALLBACK_GAS,
+        ))
+    }
+
+    /// Counts token-weighted ballot once voter's balance is known.
+    ///
+    /// # Panics
+    ///
+    /// * Election should still be running and not finalized,
+    ///   since it could end while balance was queried.
+    /// * Balance query should succeed.
+    /// * Balance should be at least election's minimum.
+    /// * User shouldn't try to vote more than once.
+    #[private]
+    pub fn on_token_balance(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        candidate_id: u8,
+        voter_id: VoterId,
+        #[callback_result] balance: Result<U128, PromiseError>,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self
+            .elections
+            .get(&(organization_id.clone(), election_id_parsed))
+            .expect(NOT_FOUND_ERROR);
+        self.assert_accepts_callback_ballot(organization_id, election_id_parsed, &election);
+        let token_gate = election.token_gate.as_ref().unwrap();
+        let balance = match balance {
+            Ok(U128(balance)) => balance,
+            Err(_) => panic!("Failed to get token balance"),
+        };
+        assert!(
+            balance >= token_gate.min_balance,
+            "Token balance is below the election minimum"
+        );
+        // Checked again, since another ballot of the voter could be counted meanwhile.
+        self.record_ballot(
+            organization_id,
+            election_id_parsed,
+            Voter::Account(voter_id),
+        );
+
+        let candidate_key = &(organization_id.clone(), election_id_parsed, candidate_id);
+        let votes = self.votes.get(candidate_key).unwrap_or(0);
+        self.votes
+            .insert(candidate_key, &(votes + token_gate.weight(balance)));
+        self.record_first_vote(organization_id, election_id_parsed, &election, candidate_id);
+    }
+
+    /// Vote with an NFT pass in an NFT-gated election.
+    ///
+    /// Every token votes once, so account holding several tokens votes several times.
+    /// Token ownership is checked in the callback.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `candidate_id` - u8 id
+    /// * `token_id` - id of caller's token from the election NFT contract
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * `organization_id` & `election_id` & `candidate_id` should be a valid combination.
+    /// * Election should be NFT-gated.
+    /// * Current date should be between start and end dates of the election.
+    /// * Caller should be on the election allowlist if election has one.
+    /// * Token shouldn't vote more than once.
+    pub fn vote_with_nft(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        candidate_id: u8,
+        token_id: String,
+    ) -> Promise {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self.open_election(organization_id, election_id_parsed);
+        let nft_contract = election
+            .nft_contract
+            .as_ref()
+            .expect("Election isn't NFT-gated");
+        assert_candidate(&election, candidate_id);
+        let voter_id = env::predecessor_account_id();
+        self.assert_allowed(organization_id, election_id_parsed, &election, &voter_id);
+        assert!(
+            !self.have_token_voted(organization_id, election_id, token_id.clone()),
+            "Token already voted"
+        );
+
+        ext_nft::nft_token(token_id.clone(), nft_contract.clone(), 0, NFT_TOKEN_GAS).then(
+            ext_self::on_nft_token(
+                organization_id.clone(),
+                election_id.clone(),
+                candidate_id,
+                token_id,
+                voter_id,
+                env::current_account_id(),
+                0,
+                VOTE_CALLBACK_GAS,
+            ),
+        )
+    }
+
+    /// Counts ballot cast with an NFT pass once token owner is known.
+    ///
+    /// # Panics
+    ///
+    /// * Election should still be running and not finalized,
+    ///   since it could end while token was queried.
+    /// * Token query should succeed.
+    /// * Voter should own the token.
+    /// * Token shouldn't vote more than once.
+    #[private]
+    pub fn on_nft_token(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        candidate_id: u8,
+        token_id: String,
+        voter_id: VoterId,
+        #[callback_result] token: Result<Option<NftToken>, PromiseError>,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self
+            .elections
+            .get(&(organization_id.clone(), election_id_parsed))
+            .expect(NOT_FOUND_ERROR);
+        self.assert_accepts_callback_ballot(organization_id, election_id_parsed, &election);
+        let token = match token {
+            Ok(Some(token)) => token,
+            Ok(None) => panic!("Token not found"),
+            Err(_) => panic!("Failed to get token"),
+        };
+        assert!(
+            token.token_id == token_id && token.owner_id == voter_id,
+            "Account doesn't own the token"
+        );
+        // Checked again, since another ballot with the token could be counted meanwhile.
+        self.record_ballot(organization_id, election_id_parsed, Voter::Nft(token_id));
+
+        let candidate_key = &(organization_id.clone(), election_id_parsed, candidate_id);
+        let votes = self.votes.get(candidate_key).unwrap_or(0);
+        self.votes.insert(candidate_key, &(votes + 1));
+        self.record_first_vote(organization_id, election_id_parsed, &election, candidate_id);
+    }
+
+    /// Checks if NFT pass has already voted in an NFT-gated election.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `token_id` - token id from the election NFT contract
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` can not be parsed as u128
+    pub fn have_token_voted(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        token_id: String,
+    ) -> bool {
+        self.ballot_voters.contains(&(
+            organization_id.clone(),
+            election_id.parse().unwrap(),
+            Voter::Nft(token_id),
+        ))
+    }
+
+    /// Vote anonymously in an election with anonymous ballots.
+    ///
+    /// Could be submitted by any account, e.g. a relayer, since voter is identified by nullifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `candidate_id` - u8 id
+    /// * `nullifier` - base64 encoded voter's nullifier for the election
+    /// * `proof` - base64 encoded Groth16 proof of membership
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election should have anonymous ballots.
+    /// * Nullifier & proof should be valid.
+    /// * Current date should be between start and end dates of the election.
+    /// * Nullifier shouldn't be used more than once.
+    pub fn vote_anonymous(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        candidate_id: u8,
+        nullifier: Base64VecU8,
+        proof: Base64VecU8,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self.open_election(organization_id, election_id_parsed);
+        let anonymity = election
+            .anonymity
+            .as_ref()
+            .expect("Election doesn't accept anonymous ballots");
+        assert_candidate(&election, candidate_id);
+        let nullifier = membership::decode_field(&nullifier.0).expect("Invalid nullifier");
+        let context = (organization_id.clone(), election_id_parsed)
+            .try_to_vec()
+            .unwrap();
+        assert!(
+            membership::verify(
+                &membership::decode_verifying_key(&anonymity.verifying_key).unwrap(),
+                &proof.0,
+                membership::decode_field(&anonymity.voters_root).unwrap(),
+                nullifier,
+                candidate_id,
+                membership::election_field(&context),
+            ),
+            "Invalid membership proof"
+        );
+        self.record_ballot(
+            organization_id,
+            election_id_parsed,
+            Voter::Nullifier(membership::encode_field(&nullifier)),
+        );
+
+        let candidate_key = &(organization_id.clone(), election_id_parsed, candidate_id);
+        let votes = self.votes.get(candidate_key).unwrap_or(0);
+        self.votes.insert(candidate_key, &(votes + 1));
+        self.record_first_vote(organization_id, election_id_parsed, &election, candidate_id);
+    }
+
+    /// Vote with a credential blind-signed by the organization.
+    ///
+    /// Meant to be called from a fresh account, so that ballot can't be linked to voter.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `candidate_id` - u8 id
+    /// * `token` - base64 encoded credential token
+    /// * `signature` - base64 encoded unblinded signature of the token,
+    ///   see [IssuerKeyInput](struct.IssuerKeyInput.html)
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election should accept ballots with credentials.
+    /// * Signature should be valid.
+    /// * Current date should be between start and end dates of the election.
+    /// * Token shouldn't be spent already.
+    pub fn vote_with_credential(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        candidate_id: u8,
+        token: Base64VecU8,
+        signature: Base64VecU8,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self.open_election(organization_id, election_id_parsed);
+        let key = election
+            .credentials
+            .as_ref()
+            .expect("Election doesn't accept ballots with credentials");
+        assert_candidate(&election, candidate_id);
+        let message = (
+            organization_id.clone(),
+            election.first_round_id(election_id_parsed),
+            token.0.clone(),
+        )
+            .try_to_vec()
+            .unwrap();
+        assert!(
+            rsa::verify(&key.modulus, &key.exponent, &message, &signature.0),
+            "Invalid credential signature"
+        );
+        self.record_ballot(
+            organization_id,
+            election_id_parsed,
+            Voter::Token(env::sha256(&token.0).try_into().unwrap()),
+        );
+
+        let candidate_key = &(organization_id.clone(), election_id_parsed, candidate_id);
+        let votes = self.votes.get(candidate_key).unwrap_or(0);
+        self.votes.insert(candidate_key, &(votes + 1));
+        self.record_first_vote(organization_id, election_id_parsed, &election, candidate_id);
+    }
+
+    /// Commit secret ballot in an election with secret ballots.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `commitment` - base64 encoded `sha256(candidate_id || salt)`,
+    ///   where `candidate_id` is a single byte and `salt` is random bytes kept by the voter.
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election should have secret ballots.
+    /// * Commitment should be 32 bytes long.
+    /// * Current date should be between start and end dates of the election.
+    /// * User shouldn't try to vote more than once.
+    pub fn commit_vote(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        commitment: Base64VecU8,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self.open_election(organization_id, election_id_parsed);
+        assert!(
+            election.reveal_end.is_some(),
+            "Election doesn't accept committed ballots"
+        );
+        let commitment: Vec<u8> = commitment.into();
+        assert_eq!(commitment.len(), 32, "Commitment should be a sha256 hash");
+        let ballot_id = self.record_voter(organization_id, election_id_parsed, &election);
+
+        let voter = env::predecessor_account_id();
+        self.commitments.insert(
+            &(organization_id.clone(), election_id_parsed, voter.clone()),
+            &commitment,
+        );
+        self.committers.insert(
+            &(organization_id.clone(), election_id_parsed, ballot_id),
+            &voter,
+        );
+    }
+
+    /// Reveal previously committed ballot. Only revealed ballots are counted.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `candidate_id` - u8 id
+    /// * `salt` - base64 encoded salt used for the commitment
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election should have secret ballots.
+    /// * Current date should be between end and reveal end dates of the election.
+    /// * User should have an unrevealed commitment.
+    /// * Candidate & salt should match the commitment.
+    pub fn reveal_vote(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        candidate_id: u8,
+        salt: Base64VecU8,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self
+            .elections
+            .get(&(organization_id.clone(), election_id_parsed))
+            .expect(NOT_FOUND_ERROR);
+        let reveal_end = election
+            .reveal_end
+            .expect("Election doesn't accept committed ballots");
+        assert!(
+            election.end < env::block_timestamp(),
+            "Reveal phase not started yet"
+        );
+        assert!(
+            reveal_end > env::block_timestamp(),
+            "Reveal phase already ended"
+        );
+        assert_candidate(&election, candidate_id);
+        let commitment_key = &(
+            organization_id.clone(),
+            election_id_parsed,
+            env::predecessor_account_id(),
+        );
+        let commitment = self
+            .commitments
+            .get(commitment_key)
+            .expect("No unrevealed commitment found");
+        let mut preimage = vec![candidate_id];
+        preimage.extend(Vec::<u8>::from(salt));
+        assert_eq!(
+            env::sha256(&preimage),
+            commitment,
+            "Ballot doesn't match the commitment"
+        );
+        self.commitments.remove(commitment_key);
+
+        let candidate_key = &(organization_id.clone(), election_id_parsed, candidate_id);
+        let votes = self.votes.get(candidate_key).unwrap_or(0);
+        self.votes.insert(candidate_key, &(votes + 1));
+        let election_key = &(organization_id.clone(), election_id_parsed);
+        let revealed = self.revealed_counts.get(election_key).unwrap_or(0);
+        self.revealed_counts.insert(election_key, &(revealed + 1));
+    }
+
+    /// Vote in an election with encrypted ballots.
+    ///
+    /// Ballot ciphertexts are added to encrypted tallies of candidates,
+    /// which are decrypted by trustees once election has ended.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `ballot` - [EncryptedBallot](struct.EncryptedBallot.html) with a single choice
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election should have encrypted ballots.
+    /// * Every candidate should have a valid ciphertext.
+    /// * Ballot proofs should be valid.
+    /// * Current date should be between start and end dates of the election.
+    /// * User shouldn't try to vote more than once.
+    pub fn vote_encrypted(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        ballot: EncryptedBallot,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self.open_election(organization_id, election_id_parsed);
+        let encryption = election
+            .encryption
+            .as_ref()
+            .expect("Election doesn't accept encrypted ballots");
+        assert_eq!(
+            ballot.ciphertexts.len(),
+            election.candidates.len(),
+            "Every candidate should have a ciphertext"
+        );
+        let ciphertexts: Vec<elgamal::Ciphertext> = ballot
+            .ciphertexts
+            .iter()
+            .map(|ciphertext| {
+                elgamal::Ciphertext::from_bytes(&ciphertext.0).expect("Invalid ciphertext")
+            })
+            .collect();
+        let proofs: Vec<Vec<u8>> = ballot.proofs.into_iter().map(Vec::from).collect();
+        let context = (
+            organization_id.clone(),
+            election_id_parsed,
+            env::predecessor_account_id(),
+        )
+            .try_to_vec()
+            .unwrap();
+        assert!(
+            elgamal::verify_ballot(
+                &context,
+                elgamal::decode_point(&encryption.public_key).unwrap(),
+                &ciphertexts,
+                &proofs,
+                &ballot.sum_proof.0,
+            ),
+            "Invalid ballot proof"
+        );
+        self.record_voter(organization_id, election_id_parsed, &election);
+
+        let key = &(organization_id.clone(), election_id_parsed);
+        let tallies: Vec<[u8; 64]> = self
+            .encrypted_tallies(key, &election)
+            .iter()
+            .zip(&ciphertexts)
+            .map(|(tally, ciphertext)| tally.add(ciphertext).to_bytes())
+            .collect();
+        self.encrypted_tallies.insert(key, &tallies);
+    }
+
+    /// Submit caller's partial decryption of an encrypted election tallies.
+    ///
+    /// Once threshold of trustees have submitted theirs, plaintext tallies could be submitted,
+    /// see [submit_tallies](#method.submit_tallies).
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `shares` - base64 encoded `x·a` for every candidate tally `(a, b)`,
+    ///   where `x` is trustee's secret key share
+    /// * `proofs` - Chaum-Pedersen proofs `c || s` of `log_G(public_share) == log_a(share)`
+    ///   for every share, bound to borsh serialized `(organization_id, election_id, candidate_id)`
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election not found.
+    /// * Election should have encrypted ballots.
+    /// * Election should be ended and not finalized yet.
+    /// * Only election trustees are allowed to call this function, once.
+    /// * Every candidate tally should have a share with a valid proof.
+    pub fn submit_decryption(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        shares: Vec<Base64VecU8>,
+        proofs: Vec<Base64VecU8>,
+    ) {
+        let key = (organization_id.clone(), election_id.parse().unwrap());
+        let election = self.elections.get(&key).expect(NOT_FOUND_ERROR);
+        let encryption = election
+            .encryption
+            .as_ref()
+            .expect("Election doesn't have encrypted ballots");
+        assert!(
+            election.end < env::block_timestamp(),
+            "Election not ended yet"
+        );
+        assert!(
+            !self.results.contains_key(&key),
+            "Election already finalized"
+        );
+        let trustee = env::predecessor_account_id();
+        let public_share = encryption
+            .trustees
+            .iter()
+            .find(|(account_id, _)| *account_id == trustee)
+            .map(|(_, share)| elgamal::decode_point(share).unwrap())
+            .expect("Only trustees can decrypt tallies");
+        let share_key = &(key.0.clone(), key.1, trustee);
+        assert!(
+            !self.decryption_shares.contains_key(share_key),
+            "Decryption already submitted"
+        );
+        assert!(
+            shares.len() == election.candidates.len() && proofs.len() == shares.len(),
+            "Every candidate tally should be decrypted"
+        );
+
+        let tallies = self.encrypted_tallies(&key, &election);
+        let shares: Vec<[u8; 32]> = shares
+            .into_iter()
+            .zip(proofs)
+            .enumerate()
+            .map(|(i, (share, proof))| {
+                let share = to_key(&share);
+                let candidate_id: CandidateId = i.try_into().unwrap();
+                let context = (key.0.clone(), key.1, candidate_id).try_to_vec().unwrap();
+                assert!(
+                    elgamal::decode_point(&share).is_some_and(|share| {
+                        elgamal::verify_decryption(
+                            &context,
+                            public_share,
+                            &tallies[i],
+                            share,
+                            &proof.0,
+                        )
+                    }),
+                    "Invalid decryption of candidate {}",
+                    candidate_id
+                );
+                share
+            })
+            .collect();
+        self.decryption_shares.insert(share_key, &shares);
+    }
+
+    /// Submit plaintext tallies of an encrypted election.
+    ///
+    /// Could be called by anyone once threshold of trustees submitted their decryptions.
+    /// Tallies are computed off-chain from the encrypted tallies and decryption shares,
+    /// contract only checks that every one of them matches its ciphertext.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `tallies` - votes of every candidate, in candidates order
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election not found.
+    /// * Election should have encrypted ballots.
+    /// * Election should be ended and not finalized yet.
+    /// * Tallies shouldn't be submitted yet.
+    /// * Not enough trustees decrypted tallies.
+    /// * Every candidate should have a tally matching its encrypted one.
+    pub fn submit_tallies(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        tallies: Vec<U128>,
+    ) {
+        let key = (organization_id.clone(), election_id.parse().unwrap());
+        let election = self.elections.get(&key).expect(NOT_FOUND_ERROR);
+        let encryption = election
+            .encryption
+            .as_ref()
+            .expect("Election doesn't have encrypted ballots");
+        assert!(
+            election.end < env::block_timestamp(),
+            "Election not ended yet"
+        );
+        assert!(
+            !self.results.contains_key(&key),
+            "Election already finalized"
+        );
+        assert!(
+            !self.decrypted_tallies.contains(&key),
+            "Tallies already submitted"
+        );
+        assert!(
+            tallies.len() == election.candidates.len(),
+            "Every candidate tally should be submitted"
+        );
+        let decryptions = self.threshold_decryptions(&key, encryption);
+
+        for (i, (tally, U128(votes))) in self
+            .encrypted_tallies(&key, &election)
+            .iter()
+            .zip(tallies)
+            .enumerate()
+        {
+            let shares: Vec<_> = decryptions
+                .iter()
+                .map(|(index, shares)| (*index, elgamal::decode_point(&shares[i]).unwrap()))
+                .collect();
+            assert!(
+                elgamal::verify_tally(tally, &shares, votes),
+                "Invalid tally of candidate {}",
+                i
+            );
+            self.votes
+                .insert(&(key.0.clone(), key.1, i.try_into().unwrap()), &votes);
+        }
+        self.decrypted_tallies.insert(&key);
+    }
+
+    /// Vote in an election with ranked ballots:
+    /// instant-runoff, Schulze, Single Transferable Vote or positional one.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `ranking` - candidate ids ordered from the most to the least preferred.
+    ///   Ranking could omit candidates voter doesn't support at all.
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election should be of `instant_runoff`, `schulze`, `stv` or `positional` kind.
+    /// * Ranking should be non-empty and contain only distinct valid candidates.
+    /// * Current date should be between start and end dates of the election.
+    /// * User shouldn't try to vote more than once.
+    pub fn vote_ranked(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        ranking: Vec<CandidateId>,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self.open_election(organization_id, election_id_parsed);
+        assert!(
+            matches!(
+                election.kind,
+                ElectionKind::InstantRunoff
+                    | ElectionKind::Schulze
+                    | ElectionKind::Stv
+                    | ElectionKind::Positional { .. }
+            ),
+            "Election doesn't accept ranked ballots"
+        );
+        assert!(!ranking.is_empty(), "Ranking should not be empty");
+        assert_distinct_candidates(&election, &ranking);
+        self.record_voter(organization_id, election_id_parsed, &election);
+
+        let candidates = election.candidates.len();
+        match election.kind {
+            ElectionKind::Schulze => {
+                let key = &(organization_id.clone(), election_id_parsed);
+                let mut matrix = self
+                    .pairwise
+                    .get(key)
+                    .unwrap_or_else(|| vec![0; candidates * candidates]);
+                schulze::record_ranking(&mut matrix, candidates, &ranking);
+                self.pairwise.insert(key, &matrix);
+            }
+            ElectionKind::Positional { rule } => {
+                for (position, candidate_id) in ranking.into_iter().enumerate() {
+                    let candidate_key =
+                        &(organization_id.clone(), election_id_parsed, candidate_id);
+                    let points = self.votes.get(candidate_key).unwrap_or(0);
+                    self.votes
+                        .insert(candidate_key, &(points + rule.points(position, candidates)));
+                }
+            }
+            _ => self.record_ranking(organization_id, election_id_parsed, ranking),
+        }
+    }
+
+    /// Vote in an approval election.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `candidate_ids` - all candidates voter approves of
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election should be of `approval` kind.
+    /// * Candidates should be distinct and valid.
+    /// * Number of candidates should be within election's min & max choices.
+    /// * Current date should be between start and end dates of the election.
+    /// * User shouldn't try to vote more than once.
+    pub fn vote_approval(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        candidate_ids: Vec<CandidateId>,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self.open_election(organization_id, election_id_parsed);
+        let (min, max) = match election.kind {
+            ElectionKind::Approval {
+                min_choices,
+                max_choices,
+            } => election.choices_limits(min_choices, max_choices),
+            _ => panic!("Election doesn't accept approval ballots"),
+        };
+        assert!(
+            (min..=max).contains(&candidate_ids.len()),
+            "Expected from {} to {} candidates",
+            min,
+            max
+        );
+        assert_distinct_candidates(&election, &candidate_ids);
+        self.record_voter(organization_id, election_id_parsed, &election);
+
+        for candidate_id in candidate_ids {
+            let candidate_key = &(organization_id.clone(), election_id_parsed, candidate_id);
+            let votes = self.votes.get(candidate_key).unwrap_or(0);
+            self.votes.insert(candidate_key, &(votes + 1));
+        }
+    }
+
+    /// Vote in a score election.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `scores` - score for every candidate, in candidates order
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election should be of `score` kind.
+    /// * Every candidate should be scored within election's scale.
+    /// * Current date should be between start and end dates of the election.
+    /// * User shouldn't try to vote more than once.
+    pub fn vote_scores(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        scores: Vec<u8>,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self.open_election(organization_id, election_id_parsed);
+        let max_score = match election.kind {
+            ElectionKind::Score { max_score } => max_score,
+            _ => panic!("Election doesn't accept score ballots"),
+        };
+        assert_eq!(
+            scores.len(),
+            election.candidates.len(),
+            "Every candidate should be scored"
+        );
+        assert!(
+            scores.iter().all(|score| *score <= max_score),
+            "Score should be between 0 and {}",
+            max_score
+        );
+        self.record_voter(organization_id, election_id_parsed, &election);
+
+        for (i, score) in scores.into_iter().enumerate() {
+            let candidate_key = &(
+                organization_id.clone(),
+                election_id_parsed,
+                i.try_into().unwrap(),
+            );
+            let mut tally = self.scores.get(candidate_key).unwrap_or_default();
+            tally.total += u128::from(score);
+            tally.ballots += 1;
+            self.scores.insert(candidate_key, &tally);
+        }
+    }
+
+    /// Vote in a majority judgment election.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `grades` - grade index for every candidate, in candidates order.
+    ///   Grades are indexed from the worst (`0`) to the best one.
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election should be of `majority_judgment` kind.
+    /// * Every candidate should be graded with one of election grades.
+    /// * Current date should be between start and end dates of the election.
+    /// * User shouldn't try to vote more than once.
+    pub fn vote_grades(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        grades: Vec<u8>,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self.open_election(organization_id, election_id_parsed);
+        let labels = match election.kind {
+            ElectionKind::MajorityJudgment { ref grades } => grades.len(),
+            _ => panic!("Election doesn't accept graded ballots"),
+        };
+        assert_eq!(
+            grades.len(),
+            election.candidates.len(),
+            "Every candidate should be graded"
+        );
+        assert!(
+            grades.iter().all(|grade| usize::from(*grade) < labels),
+            "Grade should be between 0 and {}",
+            labels - 1
+        );
+        self.record_voter(organization_id, election_id_parsed, &election);
+
+        for (i, grade) in grades.into_iter().enumerate() {
+            let candidate_key = &(
+                organization_id.clone(),
+                election_id_parsed,
+                i.try_into().unwrap(),
+            );
+            let mut histogram = self
+                .grades
+                .get(candidate_key)
+                .unwrap_or_else(|| vec![0; labels]);
+            histogram[usize::from(grade)] += 1;
+            self.grades.insert(candidate_key, &histogram);
+        }
+    }
+
+    /// Vote in a quadratic election.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `allocations` - pairs of candidate id & number of votes for that candidate
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election should be of `quadratic` kind.
+    /// * Candidates should be distinct and valid, each one getting at least one vote.
+    /// * Sum of squared votes shouldn't exceed voice credits of the election.
+    /// * Current date should be between start and end dates of the election.
+    /// * User shouldn't try to vote more than once.
+    pub fn vote_quadratic(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        allocations: Vec<(CandidateId, u32)>,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self.open_election(organization_id, election_id_parsed);
+        let credits = match election.kind {
+            ElectionKind::Quadratic { credits } => credits,
+            _ => panic!("Election doesn't accept quadratic ballots"),
+        };
+        assert_allocations(&election, &allocations);
+        let cost: u64 = allocations
+            .iter()
+            .map(|(_, votes)| u64::from(*votes) * u64::from(*votes))
+            .sum();
+        assert!(
+            cost <= u64::from(credits),
+            "Ballot costs {} credits, only {} available",
+            cost,
+            credits
+        );
+        self.record_voter(organization_id, election_id_parsed, &election);
+
+        self.add_votes(organization_id, election_id_parsed, allocations);
+    }
+
+    /// Vote in a cumulative election.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `allocations` - pairs of candidate id & number of points given to that candidate
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election should be of `cumulative` kind.
+    /// * Candidates should be distinct and valid, each one getting at least one point.
+    /// * Sum of points shouldn't exceed points budget of the election.
+    /// * Current date should be between start and end dates of the election.
+    /// * User shouldn't try to vote more than once.
+    pub fn vote_cumulative(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        allocations: Vec<(CandidateId, u32)>,
+    ) {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self.open_election(organization_id, election_id_parsed);
+        let budget = match election.kind {
+            ElectionKind::Cumulative { points } => points,
+            _ => panic!("Election doesn't accept cumulative ballots"),
+        };
+        assert_allocations(&election, &allocations);
+        let total: u64 = allocations
+            .iter()
+            .map(|(_, points)| u64::from(*points))
+            .sum();
+        assert!(
+            total <= u64::from(budget),
+            "Ballot distributes {} points, only {} available",
+            total,
+            budget
+        );
+        self.record_voter(organization_id, election_id_parsed, &election);
+
+        self.add_votes(organization_id, election_id_parsed, allocations);
+    }
+
+    /// Compute and store final results of an election.
+    ///
+    /// Could be called by anyone once election (including its reveal phase) has ended.
+    /// Plaintext tallies of an election with encrypted ballots should be submitted first,
+    /// see [submit_tallies](#method.submit_tallies).
+    /// Delegated ballots of an election with delegation should be counted first,
+    /// see [count_delegated_ballots](#method.count_delegated_ballots).
+    /// Creates runoff between the top two candidates if election has one configured
+    /// and nobody passed its threshold. Runoff starts immediately.
+    /// Nobody is elected without quorum or if winner didn't get majority required.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election not found.
+    /// * Election should be ended.
+    /// * Election shouldn't be finalized yet.
+    /// * Tallies of an election with encrypted ballots should be submitted.
+    /// * Delegated ballots should be counted in an election with delegation.
+    pub fn finalize_election(&mut self, organization_id: &OrganizationId, election_id: &String) {
+        let key = (organization_id.clone(), election_id.parse().unwrap());
+        let election = self.elections.get(&key).expect(NOT_FOUND_ERROR);
+        assert!(
+            election.counting_end() < env::block_timestamp(),
+            "Election not ended yet"
+        );
+        assert!(
+            !self.results.contains_key(&key),
+            "Election already finalized"
+        );
+        assert!(
+            election.encryption.is_none() || self.decrypted_tallies.contains(&key),
+            "Tallies not submitted yet"
+        );
+        assert!(
+            !election.delegation || self.delegators_counted(&key, &election),
+            "Delegated ballots not counted yet"
+        );
+
+        let seats = usize::from(election.seats);
+        let mut result = match election.kind {
+            ElectionKind::Plurality
+            | ElectionKind::Approval { .. }
+            | ElectionKind::Quadratic { .. }
+            | ElectionKind::Positional { .. }
+            | ElectionKind::Cumulative { .. } => ElectionResult {
+                elected: top_candidates(&self.candidate_votes(&key, &election), seats),
+                runoff: None,
+                outcome: Outcome::Elected,
+                required_turnout: None,
+                tie_break: None,
+                quota: None,
+                rounds: vec![],
+            },
+            ElectionKind::Score { .. } => ElectionResult {
+                elected: top_candidates(&self.candidate_scores(&key, &election), seats),
+                runoff: None,
+                outcome: Outcome::Elected,
+                required_turnout: None,
+                tie_break: None,
+                quota: None,
+                rounds: vec![],
+            },
+            ElectionKind::InstantRunoff => {
+                let (rank, seed) = self.tie_ranks(&key, &election);
+                let (winner, rounds) = irv::instant_runoff(
+                    election.candidates.len(),
+                    &self.stored_rankings(&key),
+                    &rank,
+                );
+                ElectionResult {
+                    elected: winner.into_iter().collect(),
+                    runoff: None,
+                    outcome: Outcome::Elected,
+                    required_turnout: None,
+                    tie_break: counting_ties(&rounds, seed),
+                    quota: None,
+                    rounds,
+                }
+            }
+            ElectionKind::Schulze => {
+                let candidates = election.candidates.len();
+                let paths =
+                    schulze::strongest_paths(&self.pairwise_matrix(&key, &election), candidates);
+                let winners = schulze::winners(&paths, candidates);
+                ElectionResult {
+                    elected: match winners[..] {
+                        [winner] => vec![winner],
+                        _ => vec![],
+                    },
+                    runoff: None,
+                    outcome: Outcome::Elected,
+                    required_turnout: None,
+                    tie_break: None,
+                    quota: None,
+                    rounds: vec![],
+                }
+            }
+            ElectionKind::MajorityJudgment { .. } => {
+                let histograms = self.candidate_histograms(&key, &election);
+                ElectionResult {
+                    elected: top_candidates_by(histograms.len(), seats, |a, b| {
+                        judgment::compare(&histograms[a], &histograms[b])
+                    }),
+                    runoff: None,
+                    outcome: Outcome::Elected,
+                    required_turnout: None,
+                    tie_break: None,
+                    quota: None,
+                    rounds: vec![],
+                }
+            }
+            ElectionKind::Stv => {
+                let (rank, seed) = self.tie_ranks(&key, &election);
+                let (elected, quota, rounds) = stv::single_transferable_vote(
+                    election.candidates.len(),
+                    seats,
+                    &self.stored_rankings(&key),
+                    &rank,
+                );
+                ElectionResult {
+                    elected,
+                    runoff: None,
+                    outcome: Outcome::Elected,
+                    required_turnout: None,
+                    tie_break: counting_ties(&rounds, seed),
+                    quota: Some(quota),
+                    rounds,
+                }
+            }
+        };
+        result.required_turnout = election
+            .quorum
+            .as_ref()
+            .map(|quorum| self.required_turnout(&key, &election, quorum));
+        let turnout = self.counted_ballots(&key, &election);
+        if result
+            .required_turnout
+            .is_some_and(|required| turnout < required)
+        {
+            result.elected = vec![];
+            result.outcome = Outcome::NoQuorum;
+        } else if let Some(runoff) = &election.runoff {
+            let votes = self.candidate_votes(&key, &election);
+            let total: u128 = votes.iter().sum();
+            let leader = votes.iter().max().cloned().unwrap_or(0);
+            if total > 0 && leader * 100 <= total * u128::from(runoff.threshold) {
+                let (finalists, tie) = self.runoff_candidates(&key, &election, &votes);
+                result.elected = vec![];
+                result.runoff = Some(self.create_runoff(&key, &election, runoff, &finalists));
+                result.tie_break = tie;
+                result.outcome = Outcome::Runoff;
+            }
+        } else if election.majority != Majority::Plurality {
+            let votes = self.candidate_votes(&key, &election);
+            let nota = self
+                .blank_votes
+                .get(&(key.0.clone(), key.1, BlankOption::Nota))
+                .unwrap_or(0);
+            let total = votes.iter().sum::<u128>() + nota;
+            // Tied leaders can't get more than a half of votes.
+            let reached = match result.elected[..] {
+                [winner] => election.majority.reached(votes[usize::from(winner)], total),
+                _ => false,
+            };
+            if !reached {
+                result.elected = vec![];
+                result.outcome = Outcome::ThresholdNotMet;
+            }
+        }
+        if result.outcome == Outcome::Elected && result.elected.len() < seats {
+            result.outcome = Outcome::Tie;
+            // Ties of ranked ballots count are broken during it.
+            if election.tie_break != TieBreak::None
+                && !matches!(
+                    election.kind,
+                    ElectionKind::InstantRunoff | ElectionKind::Stv
+                )
+            {
+                let tied = self.tied_candidates(&key, &election);
+                let tie = self.resolve_tie(&key, &election, tied, seats - result.elected.len());
+                if !tie.chosen.is_empty() {
+                    result.elected.extend(&tie.chosen);
+                    result.outcome = Outcome::Elected;
+                }
+                result.tie_break = Some(tie);
+            }
+        }
+        self.results.insert(&key, &result);
+    }
+
+    /// Choose among candidates tied at finalization of caller organization's election,
+    /// whose ties are broken by the organization.
+    ///
+    /// # Arguments
+    ///
+    /// * `election_id` - String id of caller organization's election
+    /// * `candidate_ids` - tied candidates filling the seats left, best first
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` can not be parsed as u128
+    /// * Election not found.
+    /// * Election ties should be broken by the organization.
+    /// * Election should be finalized with a tie.
+    /// * Exactly as many distinct tied candidates as seats left should be chosen.
+    pub fn break_tie(&mut self, election_id: &String, candidate_ids: Vec<CandidateId>) {
+        let key = (env::predecessor_account_id(), election_id.parse().unwrap());
+        let election = self.elections.get(&key).expect(NOT_FOUND_ERROR);
+        assert!(
+            election.tie_break == TieBreak::Organization,
+            "Election ties are not broken by the organization"
+        );
+        let mut result = self.results.get(&key).expect("Election not finalized");
+        assert!(
+            result.outcome == Outcome::Tie,
+            "Election has no tie to break"
+        );
+        assert_distinct_candidates(&election, &candidate_ids);
+        let seats = usize::from(election.seats) - result.elected.len();
+        assert!(
+            candidate_ids.len() == seats,
+            "Expected {} tied candidates",
+            seats
+        );
+        let mut tie = result.tie_break.take().unwrap();
+        for candidate_id in &candidate_ids {
+            assert!(
+                tie.tied.contains(candidate_id),
+                "Candidate {} is not tied",
+                candidate_id
+            );
+        }
+        result.elected.extend(&candidate_ids);
+        result.outcome = Outcome::Elected;
+        tie.chosen = candidate_ids;
+        result.tie_break = Some(tie);
+        self.results.insert(&key, &result);
+    }
+
+    /// Count ballots of delegators who didn't vote directly in an ended election
+    /// for their delegates' choices.
+    ///
+    /// Delegators are counted in batches, call it until all of them are counted.
+    /// Returns `true` once there are no more delegators left.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `limit` - maximum number of delegators to count
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` should be parsed as u128.
+    /// * Election not found.
+    /// * Election should support delegation.
+    /// * Election should be ended.
+    /// * Election shouldn't be finalized yet.
+    pub fn count_delegated_ballots(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        limit: u64,
+    ) -> bool {
+        let key = (organization_id.clone(), election_id.parse().unwrap());
+        let election = self.elections.get(&key).expect(NOT_FOUND_ERROR);
+        assert!(election.delegation, "Election doesn't support delegation");
+        assert!(
+            election.end < env::block_timestamp(),
+            "Election not ended yet"
+        );
+        assert!(
+            !self.results.contains_key(&key),
+            "Election already finalized"
+        );
+        let scope = Some(election.first_round_id(key.1));
+        let (mut scope_counted, mut counted) =
+            self.counted_delegators.get(&key).unwrap_or_default();
+        let mut ballots = 0;
+        for _ in 0..limit {
+            let delegator = if let Some(delegator) =
+                self.listed_delegator(organization_id, scope, scope_counted, &election)
+            {
+                scope_counted += 1;
+                delegator
+            } else if let Some(delegator) =
+                self.listed_delegator(organization_id, None, counted, &election)
+            {
+                counted += 1;
+                // Already counted with delegators for the election.
+                if self.listed_before(organization_id, scope, &delegator, election.end) {
+                    continue;
+                }
+                delegator
+            } else {
+                break;
+            };
+            if self
+                .choices
+                .contains_key(&(key.0.clone(), key.1, delegator.clone()))
+                || !self.allowed(organization_id, key.1, &election, &delegator)
+            {
+                continue;
+            }
+            if let Some(choice) =
+                self.delegated_choice(organization_id, key.1, &election, &delegator)
+            {
+                let votes = self.choice_votes(organization_id, key.1, choice);
+                self.set_choice_votes(organization_id, key.1, choice, votes + 1);
+                ballots += 1;
+            }
+        }
+        self.counted_delegators
+            .insert(&key, &(scope_counted, counted));
+        let count = self.ballots_count.get(&key).unwrap_or(0);
+        self.ballots_count.insert(&key, &(count + ballots));
+        self.delegators_counted(&key, &election)
+    }
+
+    /// Returns committed ballots that are not revealed yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `from_index` - String index of the first ballot to check
+    /// * `limit` - maximum number of ballots to check
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` & `from_index` can not be parsed as u128
+    pub fn get_unrevealed_commitments(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        from_index: &String,
+        limit: u64,
+    ) -> Vec<CommitmentView> {
+        let election_id_parsed: ElectionId = election_id.parse().unwrap();
+        let from: BallotId = from_index.parse().unwrap();
+        (from..from + u128::from(limit))
+            .map_while(|i| {
+                self.committers
+                    .get(&(organization_id.clone(), election_id_parsed, i))
+            })
+            .filter_map(|voter| {
+                self.commitments
+                    .get(&(organization_id.clone(), election_id_parsed, voter.clone()))
+                    .map(|commitment| CommitmentView {
+                        voter,
+                        commitment: commitment.into(),
+                    })
+            })
+            .collect()
+    }
+
+    /// Checks if account is on an election allowlist.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `account_id` - [AccountId](../near_sdk/struct.AccountId.html) to check
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` can not be parsed as u128
+    /// * Election not found.
+    pub fn is_allowed(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        account_id: &VoterId,
+    ) -> bool {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self
+            .elections
+            .get(&(organization_id.clone(), election_id_parsed))
+            .expect(NOT_FOUND_ERROR);
+        self.allowlist_positions.contains_key(&(
+            organization_id.clone(),
+            election.first_round_id(election_id_parsed),
+            account_id.clone(),
+        ))
+    }
+
+    /// Returns page of an election allowlist.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `from_index` - String index of the first account to return
+    /// * `limit` - maximum number of accounts to return
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` & `from_index` can not be parsed as u128 & u64 respectively
+    /// * Election not found.
+    pub fn get_allowlist(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        from_index: &String,
+        limit: u64,
+    ) -> Vec<VoterId> {
+        let election_id_parsed = election_id.parse().unwrap();
+        let election = self
+            .elections
+            .get(&(organization_id.clone(), election_id_parsed))
+            .expect(NOT_FOUND_ERROR);
+        let list_id = election.first_round_id(election_id_parsed);
+        let from: u64 = from_index.parse().unwrap();
+        (from..from.saturating_add(limit))
+            .map_while(|i| self.allowlists.get(&(organization_id.clone(), list_id, i)))
+            .collect()
+    }
+
+    /// Returns encrypted tallies of an election with encrypted ballots, in candidates order.
+    ///
+    /// Each one is base64 encoded `a || b`, sum of all ballot ciphertexts for a candidate.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` can not be parsed as u128
+    /// * Election not found.
+    /// * Election should have encrypted ballots.
+    pub fn get_encrypted_tallies(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+    ) -> Vec<Base64VecU8> {
+        let key = (organization_id.clone(), election_id.parse().unwrap());
+        let election = self.elections.get(&key).expect(NOT_FOUND_ERROR);
+        assert!(
+            election.encryption.is_some(),
+            "Election doesn't have encrypted ballots"
+        );
+        self.encrypted_tallies(&key, &election)
+            .iter()
+            .map(|tally| tally.to_bytes().to_vec().into())
+            .collect()
+    }
+
+    /// Returns trustee's decryption shares of an encrypted election tallies, if submitted.
+    ///
+    /// Each one is base64 encoded `x·a`, see [submit_decryption](#method.submit_decryption).
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    /// * `trustee_id` - [AccountId](../near_sdk/struct.AccountId.html) of a trustee
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` can not be parsed as u128
+    pub fn get_decryption_shares(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+        trustee_id: &AccountId,
+    ) -> Option<Vec<Base64VecU8>> {
+        let key = (
+            organization_id.clone(),
+            election_id.parse().unwrap(),
+            trustee_id.clone(),
+        );
+        self.decryption_shares
+            .get(&key)
+            .map(|shares| shares.iter().map(|share| share.to_vec().into()).collect())
+    }
+
+    /// Returns pairwise preferences of a Schulze election.
+    ///
+    /// Computed from ballots cast so far, so could be used while election is running.
+    /// Strongest paths are recomputed on every call in `O(n³)` of candidates count,
+    /// which is bounded by the Schulze candidates limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` can not be parsed as u128
+    /// * Election not found.
+    /// * Election should be of `schulze` kind.
+    /// * Election's results visibility policy doesn't allow to show results yet.
+    pub fn get_pairwise(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+    ) -> PairwiseView {
+        let key = (organization_id.clone(), election_id.parse().unwrap());
+        let election = self.elections.get(&key).expect(NOT_FOUND_ERROR);
+        assert!(
+            election.kind == ElectionKind::Schulze,
+            "Pairwise preferences are tracked only for Schulze elections"
+        );
+        assert!(
+            self.results_visible(&key, &election),
+            "{}",
+            HIDDEN_RESULTS_ERROR
+        );
+        let candidates = election.candidates.len();
+        let matrix = self.pairwise_matrix(&key, &election);
+        let paths = schulze::strongest_paths(&matrix, candidates);
+
+        PairwiseView {
+            matrix: to_rows(&matrix, candidates),
+            strongest_paths: to_rows(&paths, candidates),
+            condorcet_winner: schulze::condorcet_winner(&matrix, candidates),
+            schulze_winners: schulze::winners(&paths, candidates),
+        }
+    }
+
+    /// Returns final results of an election.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - [AccountId](../near_sdk/struct.AccountId.html) of an organization
+    /// * `election_id` - String id
+    ///
+    /// # Panics
+    ///
+    /// * `election_id` can not be parsed as u128
+    /// * Election is not finalized.
+    /// * Election's results visibility policy doesn't allow to show results yet.
+    pub fn get_results(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: &String,
+    ) -> ResultView {
+        let key = (organization_id.clone(), election_id.parse().unwrap());
+        let result = self.results.get(&key).expect("Election not finalized");
+        let election = self.elections.get(&key).expect(NOT_FOUND_ERROR);
+        assert!(
+            self.results_visible(&key, &election),
+            "{}",
+            HIDDEN_RESULTS_ERROR
+        );
+        let format_votes = |votes: u128| match election.kind {
+            ElectionKind::Stv => format_fractional(votes),
+            _ => votes.to_string(),
+        };
+
+        let median_grades = match election.kind {
+            ElectionKind::MajorityJudgment { ref grades } => Some(
+                self.candidate_histograms(&key, &election)
+                    .iter()
+                    .map(|histogram| {
+                        judgment::median_grade(histogram)
+                            .map(|grade| grades[grade].clone())
+                            .unwrap_or_default()
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        };
+
+        ResultView {
+            outcome: result.outcome,
+            turnout: self.counted_ballots(&key, &election).to_string(),
+            required_turnout: result.required_turnout.map(|turnout| turnout.to_string()),
+            tie_break: result.tie_break.map(|tie| TieBreakView {
+                policy: election.tie_break.clone(),
+                tied: tie.tied,
+                chosen: tie.chosen,
+                seed: tie.seed.map(Base64VecU8),
+            }),
+            winner: result.elected.first().cloned(),
+            median_grades,
+            elected: result.elected,
+            runoff_id: result.runoff.map(|id| id.to_string()),
+            quota: result.quota.map(|quota| quota.to_string()),
+            rounds: result
+                .rounds
+                .into_iter()
+                .map(|round| RoundView {
+                    tallies: round
+                        .tallies
+                        .into_iter()
+                        .map(|(candidate, votes)| CandidateTally {
+                            candidate,
+                            votes: format_votes(votes),
+                        })
+                        .collect(),
+                    exhausted: format_votes(round.exhausted),
+                    elected: round.elected,
+                    eliminated: round.eliminated,
+                    tied: round.tied,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Elections {
+    /// Fetches election accepting votes at the moment.
+    fn open_election(&self, organization_id: &OrganizationId, election_id: ElectionId) -> Election {
+        let election = self
+            .elections
+            .get(&(organization_id.clone(), election_id))
+            .expect(NOT_FOUND_ERROR);
+        assert!(
+            election.start < env::block_timestamp(),
+            "Election not started yet"
+        );
+        assert!(
+            election.end > env::block_timestamp(),
+            "Election already ended"
+        );
+        election
+    }
+
+    /// Checks that ballot counted in a callback still could be counted.
+    fn assert_accepts_callback_ballot(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        election: &Election,
+    ) {
+        assert!(
+            election.end > env::block_timestamp(),
+            "Election already ended"
+        );
+        assert!(
+            !self
+                .results
+                .contains_key(&(organization_id.clone(), election_id)),
+            "Election already finalized"
+        );
+    }
+
+    /// Marks caller as voted. Returns id of the ballot cast.
+    ///
+    /// Caller should be on the election allowlist if election has one.
+    fn record_voter(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        election: &Election,
+    ) -> BallotId {
+        self.assert_allowed(
+            organization_id,
+            election_id,
+            election,
+            &env::predecessor_account_id(),
+        );
+        self.record_ballot(
+            organization_id,
+            election_id,
+            Voter::Account(env::predecessor_account_id()),
+        )
+    }
+
+    /// Checks that account is on the election allowlist if election has one.
+    fn assert_allowed(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        election: &Election,
+        account_id: &VoterId,
+    ) {
+        assert!(
+            self.allowed(organization_id, election_id, election, account_id),
+            "Account is not allowed to vote in the election"
+        );
+    }
+
+    /// Account is on the election allowlist or election doesn't have one.
+    fn allowed(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        election: &Election,
+        account_id: &VoterId,
+    ) -> bool {
+        !election.allowlist
+            || self.allowlist_positions.contains_key(&(
+                organization_id.clone(),
+                election.first_round_id(election_id),
+                account_id.clone(),
+            ))
+    }
+
+    /// Parses election id of a delegation, checking that delegation could be changed.
+    fn delegation_scope(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: Option<String>,
+    ) -> Option<ElectionId> {
+        assert!(
+            self.organizations.contains_key(organization_id),
+            "{}",
+            NOT_REGISTERED_ERROR
+        );
+        election_id.map(|id| {
+            let election_id = id.parse().unwrap();
+            let election = self
+                .elections
+                .get(&(organization_id.clone(), election_id))
+                .expect(NOT_FOUND_ERROR);
+            assert!(election.delegation, "Election doesn't support delegation");
+            assert!(
+                election.end > env::block_timestamp(),
+                "Election already ended"
+            );
+            election.first_round_id(election_id)
+        })
+    }
+
+    /// Delegate account's ballot goes to in an election.
+    fn effective_delegate(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        election: &Election,
+        account_id: &VoterId,
+    ) -> Option<VoterId> {
+        let scope = Some(election.first_round_id(election_id));
+        self.delegation_at(
+            &(organization_id.clone(), scope, account_id.clone()),
+            election.end,
+        )
+        .or_else(|| {
+            self.delegation_at(
+                &(organization_id.clone(), None, account_id.clone()),
+                election.end,
+            )
+        })
+    }
+
+    /// Delegate of an account in a scope according to the last change made before `time`.
+    fn delegation_at(
+        &self,
+        key: &(OrganizationId, Option<ElectionId>, VoterId),
+        time: u64,
+    ) -> Option<VoterId> {
+        let (organization_id, scope, account_id) = key;
+        let change = |index| {
+            self.delegations
+                .get(&(organization_id.clone(), *scope, account_id.clone(), index))
+                .unwrap()
+        };
+        // Changes are ordered by time, so binary search finds how many were made before `time`.
+        let (mut low, mut high) = (0, self.delegation_changes.get(key).unwrap_or(0));
+        while low < high {
+            let middle = (low + high) / 2;
+            if change(middle).since < time {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        low.checked_sub(1).and_then(|last| change(last).delegate)
+    }
+
+    /// Appends a change of account's delegation in a scope.
+    fn record_delegation(
+        &mut self,
+        key: &(OrganizationId, Option<ElectionId>, VoterId),
+        delegate: Option<VoterId>,
+    ) {
+        let (organization_id, scope, account_id) = key;
+        let index = self.delegation_changes.get(key).unwrap_or(0);
+        self.delegations.insert(
+            &(organization_id.clone(), *scope, account_id.clone(), index),
+            &Delegation {
+                delegate,
+                since: env::block_timestamp(),
+            },
+        );
+        self.delegation_changes.insert(key, &(index + 1));
+    }
+
+    /// Choice of the first account in the chain of delegates who voted directly.
+    ///
+    /// `None` if chain ends without a direct ballot, forms a cycle
+    /// or exceeds [MAX_DELEGATION_DEPTH].
+    fn delegated_choice(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        election: &Election,
+        account_id: &VoterId,
+    ) -> Option<BallotOption> {
+        let mut chain = vec![account_id.clone()];
+        while chain.len() <= MAX_DELEGATION_DEPTH {
+            let delegate = self.effective_delegate(
+                organization_id,
+                election_id,
+                election,
+                chain.last().unwrap(),
+            )?;
+            if chain.contains(&delegate) {
+                return None;
+            }
+            let choice =
+                self.choices
+                    .get(&(organization_id.clone(), election_id, delegate.clone()));
+            if let Some(choice) = choice {
+                return Some(choice.option);
+            }
+            chain.push(delegate);
+        }
+        None
+    }
+
+    /// Delegator at `index` in the list of a scope, if listed before election end.
+    ///
+    /// List is ordered by time of the first delegation, so delegators after the first one
+    /// listed at or after election end don't count either.
+    fn listed_delegator(
+        &self,
+        organization_id: &OrganizationId,
+        scope: Option<ElectionId>,
+        index: u64,
+        election: &Election,
+    ) -> Option<VoterId> {
+        self.delegators
+            .get(&(organization_id.clone(), scope, index))
+            .filter(|delegator| self.listed_before(organization_id, scope, delegator, election.end))
+    }
+
+    /// Checks if account delegated in a scope before `time`.
+    fn listed_before(
+        &self,
+        organization_id: &OrganizationId,
+        scope: Option<ElectionId>,
+        account_id: &VoterId,
+        time: u64,
+    ) -> bool {
+        self.delegations
+            .get(&(organization_id.clone(), scope, account_id.clone(), 0))
+            .is_some_and(|first| first.since < time)
+    }
+
+    /// Checks that ballots of all delegators of an election are counted.
+    fn delegators_counted(&self, key: &(OrganizationId, ElectionId), election: &Election) -> bool {
+        let (scope_counted, counted) = self.counted_delegators.get(key).unwrap_or_default();
+        let scope = Some(election.first_round_id(key.1));
+        self.listed_delegator(&key.0, scope, scope_counted, election)
+            .is_none()
+            && self
+                .listed_delegator(&key.0, None, counted, election)
+                .is_none()
+    }
+
+    /// Counts plain ballot cast by caller, replacing the previous one.
+    fn cast_plain_ballot(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        election: &Election,
+        option: BallotOption,
+        proof: Option<MerkleProof>,
+        voucher: Option<Voucher>,
+    ) {
+        assert!(
+            election.kind == ElectionKind::Plurality,
+            "Election doesn't accept single-choice ballots"
+        );
+        assert!(
+            election.reveal_end.is_none(),
+            "Election accepts only committed ballots"
+        );
+        assert!(
+            election.encryption.is_none(),
+            "Election accepts only encrypted ballots"
+        );
+        assert!(
+            election.anonymity.is_none(),
+            "Election accepts only anonymous ballots"
+        );
+        assert!(
+            election.credentials.is_none(),
+            "Election accepts only ballots with credentials"
+        );
+        assert!(
+            election.token_gate.is_none(),
+            "Election accepts only ballots weighted by tokens"
+        );
+        assert!(
+            election.nft_contract.is_none(),
+            "Election accepts only ballots with NFT passes"
+        );
+        let weight = match election.allowlist_root {
+            Some(root) => {
+                let proof = proof.expect("Merkle proof of eligibility expected");
+                let weight = proof.weight.map_or(1, |weight| weight.parse().unwrap());
+                let siblings: Vec<Vec<u8>> = proof.siblings.into_iter().map(Vec::from).collect();
+                assert!(
+                    merkle::verify(
+                        &root,
+                        &merkle::leaf(&env::predecessor_account_id(), weight),
+                        &siblings
+                    ),
+                    "Account is not in the election Merkle allowlist"
+                );
+                weight
+            }
+            None => 1,
+        };
+        if election.vouchers {
+            let voucher = voucher.expect("Voucher expected");
+            let expiry: u64 = voucher.expiry.parse().unwrap();
+            assert!(expiry > env::block_timestamp(), "Voucher expired");
+            let key = self
+                .voucher_keys
+                .get(organization_id)
+                .expect("Organization has no voucher key");
+            let message = (
+                organization_id.clone(),
+                election.first_round_id(election_id),
+                env::predecessor_account_id(),
+                expiry,
+            )
+                .try_to_vec()
+                .unwrap();
+            assert!(
+                voucher::verify(&key, &message, &voucher.signature.0),
+                "Invalid voucher signature"
+            );
+        }
+        let choice_key = &(
+            organization_id.clone(),
+            election_id,
+            env::predecessor_account_id(),
+        );
+        match self.choices.get(choice_key) {
+            Some(previous) => {
+                let votes = self.choice_votes(organization_id, election_id, previous.option);
+                self.set_choice_votes(
+                    organization_id,
+                    election_id,
+                    previous.option,
+                    votes - previous.weight,
+                );
+            }
+            None => {
+                self.record_voter(organization_id, election_id, election);
+            }
+        }
+        let listed = match option {
+            BallotOption::Candidate(candidate_id) => {
+                self.record_first_vote(organization_id, election_id, election, candidate_id)
+            }
+            BallotOption::Blank(_) => None,
+        };
+        let previous = self.choices.insert(
+            choice_key,
+            &Choice {
+                option,
+                weight,
+                listed,
+            },
+        );
+
+        let votes = self.choice_votes(organization_id, election_id, option);
+        self.set_choice_votes(organization_id, election_id, option, votes + weight);
+        if let Some(Choice {
+            option: BallotOption::Candidate(candidate_id),
+            listed: Some(position),
+            ..
+        }) = previous
+        {
+            self.unlist_first_vote(organization_id, election_id, candidate_id, position);
+        }
+    }
+
+    /// Votes for an option of plain ballots.
+    fn choice_votes(
+        &self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        option: BallotOption,
+    ) -> u128 {
+        match option {
+            BallotOption::Candidate(candidate_id) => {
+                self.votes
+                    .get(&(organization_id.clone(), election_id, candidate_id))
+            }
+            BallotOption::Blank(blank) => {
+                self.blank_votes
+                    .get(&(organization_id.clone(), election_id, blank))
+            }
+        }
+        .unwrap_or(0)
+    }
+
+    fn set_choice_votes(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        option: BallotOption,
+        votes: u128,
+    ) {
+        match option {
+            BallotOption::Candidate(candidate_id) => self.votes.insert(
+                &(organization_id.clone(), election_id, candidate_id),
+                &votes,
+            ),
+            BallotOption::Blank(blank) => self
+                .blank_votes
+                .insert(&(organization_id.clone(), election_id, blank), &votes),
+        };
+    }
+
+    /// Marks voter as voted. Returns id of the ballot cast.
+    fn record_ballot(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        voter: Voter,
+    ) -> BallotId {
+        let voted = match voter {
+            Voter::Account(account_id) => {
+                !self
+                    .voters
+                    .insert(&(organization_id.clone(), election_id, account_id))
+            }
+            voter => !self
+                .ballot_voters
+                .insert(&(organization_id.clone(), election_id, voter)),
+        };
+        assert!(!voted, "User already voted");
+
+        let election_key = &(organization_id.clone(), election_id);
+        let ballot_id = self.ballots_count.get(election_key).unwrap_or(0);
+        self.ballots_count.insert(election_key, &(ballot_id + 1));
+        ballot_id
+    }
+
+    /// Checks that caller organization's election allowlist could be changed.
+    fn assert_allowlist_editable(&self, key: &(OrganizationId, ElectionId)) {
+        let election = self.elections.get(key).expect(NOT_FOUND_ERROR);
+        assert!(
+            election.allowlist,
+            "Election is not restricted to an allowlist"
+        );
+        assert!(
+            election.start > env::block_timestamp(),
+            "Allowlist could be changed only before election start"
+        );
+    }
+
+    /// Number of ballots counted toward turnout.
+    ///
+    /// Only revealed ballots count in an election with secret ballots.
+    fn counted_ballots(&self, key: &(OrganizationId, ElectionId), election: &Election) -> u128 {
+        if election.reveal_end.is_some() {
+            self.revealed_counts.get(key).unwrap_or(0)
+        } else {
+            self.ballots_count.get(key).unwrap_or(0)
+        }
+    }
+
+    /// Number of ballots needed for a valid election.
+    fn required_turnout(
+        &self,
+        key: &(OrganizationId, ElectionId),
+        election: &Election,
+        quorum: &Quorum,
+    ) -> u128 {
+        match quorum {
+            Quorum::Ballots(ballots) => *ballots,
+            Quorum::AllowlistPercent(percent) => {
+                let size = self
+                    .allowlist_sizes
+                    .get(&(key.0.clone(), election.first_round_id(key.1)))
+                    .unwrap_or(0);
+                (u128::from(size) * u128::from(*percent)).div_ceil(100)
+            }
+        }
+    }
+
+    /// Resolves a tie of `tied` candidates for the `seats` left with election tie-break policy.
+    fn resolve_tie(
+        &self,
+        key: &(OrganizationId, ElectionId),
+        election: &Election,
+        mut tied: Vec<CandidateId>,
+        seats: usize,
+    ) -> TieResolution {
+        let mut seed = None;
+        match election.tie_break {
+            TieBreak::Random => {
+                let random_seed = env::random_seed();
+                tied.sort_by_cached_key(|candidate| {
+                    env::sha256(&[&random_seed[..], &[*candidate]].concat())
+                });
+                seed = Some(random_seed);
+            }
+            TieBreak::FirstVote => tied.sort_by_cached_key(|candidate| {
+                self.first_vote(key, *candidate).unwrap_or(u64::MAX)
+            }),
+            TieBreak::CandidateOrder => {}
+            TieBreak::None | TieBreak::Organization => {
+                return TieResolution {
+                    tied,
+                    chosen: vec![],
+                    seed,
+                }
+            }
+        }
+        TieResolution {
+            chosen: tied[..seats].to_vec(),
+            tied,
+            seed,
+        }
+    }
+
+    /// Rank of every candidate breaking ties during ranked ballots count, lower one wins.
+    ///
+    /// Candidates are ranked as listed, unless election tie-break policy orders them.
+    /// Returns block random seed of a random tie-break too.
+    fn tie_ranks(
+        &self,
+        key: &(OrganizationId, ElectionId),
+        election: &Election,
+    ) -> (Vec<usize>, Option<Vec<u8>>) {
+        let candidates = election.candidates.len();
+        let all = (0..candidates).map(|c| c.try_into().unwrap()).collect();
+        let tie = self.resolve_tie(key, election, all, candidates);
+        let mut rank: Vec<usize> = (0..candidates).collect();
+        for (i, candidate) in tie.chosen.iter().enumerate() {
+            rank[usize::from(*candidate)] = i;
+        }
+        (rank, tie.seed)
+    }
+
+    /// Two candidates with the most votes advancing to the runoff, best first.
+    ///
+    /// Tie for the last place is broken with election tie-break policy.
+    /// All tied candidates advance if policy doesn't break ties at finalization.
+    fn runoff_candidates(
+        &self,
+        key: &(OrganizationId, ElectionId),
+        election: &Election,
+        votes: &[u128],
+    ) -> (Vec<CandidateId>, Option<TieResolution>) {
+        let mut finalists = top_candidates(votes, 2);
+        if finalists.len() == 2 {
+            return (finalists, None);
+        }
+        let tied = tied_candidates_by(votes.len(), 2, |a, b| votes[a].cmp(&votes[b]));
+        let mut tie = self.resolve_tie(key, election, tied, 2 - finalists.len());
+        if tie.chosen.is_empty() {
+            tie.chosen = tie.tied.clone();
+        }
+        finalists.extend(&tie.chosen);
+        (finalists, Some(tie))
+    }
+
+    /// Candidates tied for the seats left unfilled at finalization, in candidates order.
+    fn tied_candidates(
+        &self,
+        key: &(OrganizationId, ElectionId),
+        election: &Election,
+    ) -> Vec<CandidateId> {
+        let seats = usize::from(election.seats);
+        match election.kind {
+            ElectionKind::Plurality
+            | ElectionKind::Approval { .. }
+            | ElectionKind::Quadratic { .. }
+            | ElectionKind::Positional { .. }
+            | ElectionKind::Cumulative { .. } => {
+                let votes = self.candidate_votes(key, election);
+                tied_candidates_by(votes.len(), seats, |a, b| votes[a].cmp(&votes[b]))
+            }
+            ElectionKind::Score { .. } => {
+                let scores = self.candidate_scores(key, election);
+                tied_candidates_by(scores.len(), seats, |a, b| scores[a].cmp(&scores[b]))
+            }
+            ElectionKind::Schulze => {
+                let candidates = election.candidates.len();
+                let paths =
+                    schulze::strongest_paths(&self.pairwise_matrix(key, election), candidates);
+                schulze::winners(&paths, candidates)
+            }
+            ElectionKind::MajorityJudgment { .. } => {
+                let histograms = self.candidate_histograms(key, election);
+                tied_candidates_by(histograms.len(), seats, |a, b| {
+                    judgment::compare(&histograms[a], &histograms[b])
+                })
+            }
+            ElectionKind::InstantRunoff | ElectionKind::Stv => vec![],
+        }
+    }
+
+    /// Lists a ballot for a candidate, so that the earliest one counted could break ties.
+    ///
+    /// Returns position of the ballot, if the election breaks ties by the first vote.
+    fn record_first_vote(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        election: &Election,
+        candidate_id: CandidateId,
+    ) -> Option<u64> {
+        if election.tie_break != TieBreak::FirstVote {
+            return None;
+        }
+        let key = &(organization_id.clone(), election_id, candidate_id);
+        let mut list = self.first_votes.get(key).unwrap_or_default();
+        let position = list.listed;
+        if let Some(last) = list.last {
+            let last_key = &(organization_id.clone(), election_id, candidate_id, last);
+            let mut ballot = self.candidate_ballots.get(last_key).unwrap();
+            ballot.next = Some(position);
+            self.candidate_ballots.insert(last_key, &ballot);
+        } else {
+            list.first = Some(position);
+        }
+        self.candidate_ballots.insert(
+            &(organization_id.clone(), election_id, candidate_id, position),
+            &ListedBallot {
+                cast_at: env::block_timestamp(),
+                previous: list.last,
+                next: None,
+            },
+        );
+        list.last = Some(position);
+        list.listed += 1;
+        self.first_votes.insert(key, &list);
+        Some(position)
+    }
+
+    /// Unlinks a ballot changed or retracted from the list of its candidate.
+    fn unlist_first_vote(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        candidate_id: CandidateId,
+        position: u64,
+    ) {
+        let key = &(organization_id.clone(), election_id, candidate_id);
+        let mut list = self.first_votes.get(key).unwrap();
+        let ballot = self
+            .candidate_ballots
+            .remove(&(organization_id.clone(), election_id, candidate_id, position))
+            .unwrap();
+        match ballot.previous {
+            Some(previous) => {
+                let previous_key = &(organization_id.clone(), election_id, candidate_id, previous);
+                let mut previous_ballot = self.candidate_ballots.get(previous_key).unwrap();
+                previous_ballot.next = ballot.next;
+                self.candidate_ballots
+                    .insert(previous_key, &previous_ballot);
+            }
+            None => list.first = ballot.next,
+        }
+        match ballot.next {
+            Some(next) => {
+                let next_key = &(organization_id.clone(), election_id, candidate_id, next);
+                let mut next_ballot = self.candidate_ballots.get(next_key).unwrap();
+                next_ballot.previous = ballot.previous;
+                self.candidate_ballots.insert(next_key, &next_ballot);
+            }
+            None => list.last = ballot.previous,
+        }
+        self.first_votes.insert(key, &list);
+    }
+
+    /// Time of the earliest ballot for a candidate still counted.
+    fn first_vote(
+        &self,
+        key: &(OrganizationId, ElectionId),
+        candidate_id: CandidateId,
+    ) -> Option<u64> {
+        let first = self
+            .first_votes
+            .get(&(key.0.clone(), key.1, candidate_id))?
+            .first?;
+        self.candidate_ballots
+            .get(&(key.0.clone(), key.1, candidate_id, first))
+            .map(|ballot| ballot.cast_at)
+    }
+
+    /// Candidate view with its tallies.
+    fn candidate_view(
+        &self,
+        key: &(OrganizationId, ElectionId),
+        election: &Election,
+        i: usize,
+        name: &str,
+    ) -> Candidate {
+        let candidate_key = &(key.0.clone(), key.1, i.try_into().unwrap());
+        match election.kind {
+            ElectionKind::Score { .. } => {
+                let tally = self.scores.get(candidate_key).unwrap_or_default();
+                Candidate {
+                    name: name.to_string(),
+                    votes: Some(tally.ballots.to_string()),
+                    total_score: Some(tally.total.to_string()),
+                    average_score: Some(tally.average()),
+                    ..Default::default()
+                }
+            }
+            ElectionKind::Positional {
+                rule: PositionalRule::Dowdall,
+            } => Candidate {
+                name: name.to_string(),
+                votes: Some(format_fractional(
+                    self.votes.get(candidate_key).unwrap_or(0) * VOTE_SCALE
+                        / dowdall_scale(election.candidates.len()),
+                )),
+                ..Default::default()
+            },
+            ElectionKind::MajorityJudgment { ref grades } => {
+                let histogram = self
+                    .grades
+                    .get(candidate_key)
+                    .unwrap_or_else(|| vec![0; grades.len()]);
+                Candidate {
+                    name: name.to_string(),
+                    votes: Some(histogram.iter().sum::<u128>().to_string()),
+                    histogram: Some(histogram.iter().map(u128::to_string).collect()),
+                    ..Default::default()
+                }
+            }
+            _ => Candidate {
+                name: name.to_string(),
+                votes: Some(self.votes.get(candidate_key).unwrap_or(0).to_string()),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Checks if election's visibility policy allows to show its results.
+    ///
+    /// Tallies of an election with encrypted ballots are known only once it's finalized.
+    fn results_visible(&self, key: &(OrganizationId, ElectionId), election: &Election) -> bool {
+        if election.encryption.is_some() && !self.results.contains_key(key) {
+            return false;
+        }
+        match election.results_visibility {
+            ResultsVisibility::Live => true,
+            ResultsVisibility::AfterEnd => election.counting_end() < env::block_timestamp(),
+            ResultsVisibility::AfterPublish => election.published,
+        }
+    }
+
+    /// Creates second round between the candidates advancing to it.
+    fn create_runoff(
+        &mut self,
+        key: &(OrganizationId, ElectionId),
+        parent: &Election,
+        runoff: &Runoff,
+        finalists: &[CandidateId],
+    ) -> ElectionId {
+        let now = env::block_timestamp();
+        let end = now + runoff.duration;
+        let election = Election {
+            start: now,
+            end,
+            title: format!("{} (runoff)", parent.title),
+            description: parent.description.clone(),
+            candidates: finalists
+                .iter()
+                .map(|i| parent.candidates[usize::from(*i)].clone())
+                .collect(),
+            kind: ElectionKind::Plurality,
+            seats: 1,
+            runoff: None,
+            parent: Some(key.1),
+            // Reveal phase lasts as long as in the parent election.
+            reveal_end: parent
+                .reveal_end
+                .map(|reveal_end| end + (reveal_end - parent.end)),
+            results_visibility: parent.results_visibility.clone(),
+            show_turnout: parent.show_turnout,
+            published: false,
+            encryption: parent.encryption.clone(),
+            anonymity: parent.anonymity.clone(),
+            credentials: parent.credentials.clone(),
+            allowlist: parent.allowlist,
+            allowlist_root: parent.allowlist_root,
+            token_gate: parent.token_gate.clone(),
+            nft_contract: parent.nft_contract.clone(),
+            vouchers: parent.vouchers,
+            delegation: parent.delegation,
+            blank_options: parent.blank_options.clone(),
+            quorum: parent.quorum.clone(),
+            majority: Majority::Plurality,
+            tie_break: parent.tie_break.clone(),
+        };
+
+        let id = self.organizations.get(&key.0).expect(NOT_REGISTERED_ERROR);
+        self.organizations.insert(&key.0, &(id + 1));
+        self.elections.insert(&(key.0.clone(), id), &election);
+        id
+    }
+
+    /// Adds allocated votes to candidates counters.
+    fn add_votes(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        allocations: Vec<(CandidateId, u32)>,
+    ) {
+        for (candidate_id, votes) in allocations {
+            let candidate_key = &(organization_id.clone(), election_id, candidate_id);
+            let total = self.votes.get(candidate_key).unwrap_or(0);
+            self.votes
+                .insert(candidate_key, &(total + u128::from(votes)));
+        }
+    }
+
+    /// Encrypted tallies of all candidates of an election with encrypted ballots.
+    fn encrypted_tallies(
+        &self,
+        key: &(OrganizationId, ElectionId),
+        election: &Election,
+    ) -> Vec<elgamal::Ciphertext> {
+        match self.encrypted_tallies.get(key) {
+            Some(tallies) => tallies
+                .iter()
+                .map(|tally| elgamal::Ciphertext::from_bytes(tally).unwrap())
+                .collect(),
+            None => vec![elgamal::Ciphertext::zero(); election.candidates.len()],
+        }
+    }
+
+    /// Returns decryption shares of the first threshold of trustees with their indexes.
+    ///
+    /// # Panics
+    ///
+    /// * Not enough trustees decrypted tallies.
+    fn threshold_decryptions(
+        &self,
+        key: &(OrganizationId, ElectionId),
+        encryption: &Encryption,
+    ) -> Vec<(u64, Vec<[u8; 32]>)> {
+        let decryptions: Vec<(u64, Vec<[u8; 32]>)> = (1..)
+            .zip(&encryption.trustees)
+            .filter_map(|(i, (account_id, _))| {
+                self.decryption_shares
+                    .get(&(key.0.clone(), key.1, account_id.clone()))
+                    .map(|shares| (i, shares))
+            })
+            .take(encryption.threshold.into())
+            .collect();
+        assert!(
+            decryptions.len() == usize::from(encryption.threshold),
+            "Not enough trustees decrypted tallies: {} of {}",
+            decryptions.len(),
+            encryption.threshold
+        );
+        decryptions
+    }
+
+    /// Adds a ballot to the tally of its ranking.
+    fn record_ranking(
+        &mut self,
+        organization_id: &OrganizationId,
+        election_id: ElectionId,
+        ranking: Vec<CandidateId>,
+    ) {
+        let id_key = (organization_id.clone(), election_id, ranking);
+        let id = match self.ranking_ids.get(&id_key) {
+            Some(id) => id,
+            None => {
+                let key = &(organization_id.clone(), election_id);
+                let id = self.rankings_counts.get(key).unwrap_or(0);
+                self.rankings_counts.insert(key, &(id + 1));
+                self.ranking_ids.insert(&id_key, &id);
+                id
+            }
+        };
+        let (organization_id, election_id, ranking) = id_key;
+        let key = &(organization_id, election_id, id);
+        let ballots = self.rankings.get(key).map_or(0, |tally| tally.ballots);
+        self.rankings.insert(
+            key,
+            &RankingTally {
+                ranking,
+                ballots: ballots + 1,
+            },
+        );
+    }
+
+    /// Distinct rankings stored for an election with their ballot counts.
+    fn stored_rankings(&self, key: &(OrganizationId, ElectionId)) -> Vec<(Vec<CandidateId>, u128)> {
+        (0..self.rankings_counts.get(key).unwrap_or(0))
+            .filter_map(|i| self.rankings.get(&(key.0.clone(), key.1, i)))
+            .map(|tally| (tally.ranking, tally.ballots))
+            .collect()
+    }
+
+    /// Votes counters of all election candidates.
+    fn candidate_votes(
+        &self,
+        key: &(OrganizationId, ElectionId),
+        election: &Election,
+    ) -> Vec<u128> {
+        (0..election.candidates.len())
+            .map(|i| {
+                self.votes
+                    .get(&(key.0.clone(), key.1, i.try_into().unwrap()))
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    /// Grades histograms of all majority judgment election candidates.
+    fn candidate_histograms(
+        &self,
+        key: &(OrganizationId, ElectionId),
+        election: &Election,
+    ) -> Vec<Vec<u128>> {
+        let grades = match election.kind {
+            ElectionKind::MajorityJudgment { ref grades } => grades.len(),
+            _ => 0,
+        };
+        (0..election.candidates.len())
+            .map(|i| {
+                self.grades
+                    .get(&(key.0.clone(), key.1, i.try_into().unwrap()))
+                    .unwrap_or_else(|| vec![0; grades])
+            })
+            .collect()
+    }
+
+    /// Pairwise preferences matrix of a Schulze election.
+    fn pairwise_matrix(&self, key: &(OrganizationId, ElectionId), election: &Election) -> Vec<u32> {
+        let candidates = election.candidates.len();
+        self.pairwise
+            .get(key)
+            .unwrap_or_else(|| vec![0; candidates * candidates])
+    }
+
+    /// Total scores of all election candidates.
+    fn candidate_scores(
+        &self,
+        key: &(OrganizationId, ElectionId),
+        election: &Election,
+    ) -> Vec<u128> {
+        (0..election.candidates.len())
+            .map(|i| {
+                self.scores
+                    .get(&(key.0.clone(), key.1, i.try_into().unwrap()))
+                    .unwrap_or_default()
+                    .total
+            })
+            .collect()
+    }
+}
+
+/// Candidates with the highest tallies, best first.
+///
+/// Candidates tied for the last available seat are all left out,
+/// so fewer than `seats` candidates are returned in that case.
+fn top_candidates(tallies: &[u128], seats: usize) -> Vec<CandidateId> {
+    top_candidates_by(tallies.len(), seats, |a, b| tallies[a].cmp(&tallies[b]))
+}
+
+/// Best `seats` out of `candidates` according to `compare`, where better candidate is greater.
+///
+/// Same tie rules as for [top_candidates].
+fn top_candidates_by(
+    candidates: usize,
+    seats: usize,
+    compare: impl Fn(usize, usize) -> std::cmp::Ordering,
+) -> Vec<CandidateId> {
+    let mut ranked: Vec<usize> = (0..candidates).collect();
+    ranked.sort_by(|a, b| compare(*b, *a));
+    let elected = ranked.iter().take(seats);
+    let elected: Vec<usize> = match ranked.get(seats) {
+        Some(first_losing) => elected
+            .filter(|c| compare(**c, *first_losing).is_gt())
+            .cloned()
+            .collect(),
+        None => elected.cloned().collect(),
+    };
+    elected
+        .into_iter()
+        .map(|candidate| candidate.try_into().unwrap())
+        .collect()
+}
+
+/// Ties broken during ranked ballots count, if any.
+fn counting_ties(rounds: &[irv::Round], seed: Option<Vec<u8>>) -> Option<TieResolution> {
+    let mut tied: Vec<CandidateId> = rounds
+        .iter()
+        .flat_map(|round| round.tied.iter().cloned())
+        .collect();
+    tied.sort_unstable();
+    tied.dedup();
+    (!tied.is_empty()).then(|| TieResolution {
+        tied,
+        chosen: vec![],
+        seed,
+    })
+}
+
+/// Candidates tied with the best one left out of top `seats` according to `compare`,
+/// in candidates order.
+fn tied_candidates_by(
+    candidates: usize,
+    seats: usize,
+    compare: impl Fn(usize, usize) -> std::cmp::Ordering,
+) -> Vec<CandidateId> {
+    let mut ranked: Vec<usize> = (0..candidates).collect();
+    ranked.sort_by(|a, b| compare(*b, *a));
+    match ranked.get(seats) {
+        Some(first_losing) => (0..candidates)
+            .filter(|c| compare(*c, *first_losing).is_eq())
+            .map(|candidate| candidate.try_into().unwrap())
+            .collect(),
+        None => vec![],
+    }
+}
+
+fn assert_candidate(election: &Election, candidate_id: CandidateId) {
+    assert!(
+        usize::from(candidate_id) < election.candidates.len(),
+        "Candidate {} not found",
+        candidate_id
+    );
+}
+
+fn assert_allocations(election: &Election, allocations: &[(CandidateId, u32)]) {
+    assert!(!allocations.is_empty(), "Allocations should not be empty");
+    let candidate_ids: Vec<CandidateId> = allocations.iter().map(|(c, _)| *c).collect();
+    assert_distinct_candidates(election, &candidate_ids);
+    assert!(
+        allocations.iter().all(|(_, votes)| *votes > 0),
+        "Every allocation should have at least one vote"
+    );
+}
+
+fn assert_distinct_candidates(election: &Election, candidate_ids: &[CandidateId]) {
+    for (i, candidate_id) in candidate_ids.iter().enumerate() {
+        assert_candidate(election, *candidate_id);
+        assert!(
+            !candidate_ids[..i].contains(candidate_id),
+            "Candidate {} chosen more than once",
+            candidate_id
+        );
+    }
+}
+
+/// Least common multiple of `1..=candidates`, so that every Dowdall point is a whole number.
+fn dowdall_scale(candidates: usize) -> u128 {
+    (1..=candidates as u128).fold(1, |lcm, k| {
+        let (mut a, mut b) = (lcm, k);
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        lcm / a * k
+    })
+}
+
+/// Formats fixed-point votes as a decimal, e.g. `2.500000`.
+fn format_fractional(votes: u128) -> String {
+    format!("{}.{:06}", votes / VOTE_SCALE, votes % VOTE_SCALE)
+}
+
+/// Splits flat square matrix into rows of strings for views.
+fn to_rows(matrix: &[u32], size: usize) -> Vec<Vec<String>> {
+    matrix
+        .chunks(size)
+        .map(|row| row.iter().map(u32::to_string).collect())
+        .collect()
+}
+
+/// Converts base64 encoded compressed point.
+fn to_key(key: &Base64VecU8) -> [u8; 32] {
+    key.0
+        .as_slice()
+        .try_into()
+        .expect("Keys should be 32 bytes long")
+}
+
+fn to_yocto(n: u128) -> u128 {
+    n * 10_u128.pow(24)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use super::*;
+    use chrono::{DateTime, Duration, Utc};
+    use curve25519_dalek::scalar::Scalar;
     use near_sdk::test_utils::VMContextBuilder;
     use near_sdk::{testing_env, AccountId};
 
-    const OWNER: &str = "alice.testnet";
-    const USER: &str = "bob.testnet";
-    const ORGANIZATION: &str = "org1.testnet";
-    const EXPECTED_CREATE_ELECTION_COST: u128 = 1_000_000_000_000_000_000_000_000;
+    const OWNER: &str = "alice.testnet";
+    const USER: &str = "bob.testnet";
+    const ORGANIZATION: &str = "org1.testnet";
+    const TOKEN: &str = "token.testnet";
+    const NFT: &str = "pass.testnet";
+    const EXPECTED_CREATE_ELECTION_COST: u128 = 1_000_000_000_000_000_000_000_000;
+    const ALLOWLIST_DEPOSIT: u128 = 100_000_000_000_000_000_000_000;
+
+    #[test]
+    fn should_create_organization() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+
+        contract.register_organization(&organization, None);
+
+        assert!(contract.organizations.contains_key(&organization));
+        assert_eq!(contract.organizations.get(&organization).unwrap(), 0);
+    }
+
+    #[test]
+    fn should_keep_elections_when_registering_organization_again() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        contract.register_organization(&organization, None);
+        contract.organizations.insert(&organization, &2);
+
+        contract.register_organization(
+            &organization,
+            Some(voucher::signer::public_key(&VOUCHER_SECRET).into()),
+        );
+
+        assert_eq!(contract.elections_count(&organization), "2");
+        assert!(contract.get_voucher_key(&organization).is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid voucher key")]
+    fn should_check_voucher_key() {
+        let mut contract = create_contract();
+
+        contract.register_organization(&account(ORGANIZATION), Some(vec![0; 32].into()));
+    }
+
+    #[test]
+    fn should_rotate_voucher_key() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        contract.register_organization(&organization, None);
+        contract.organizations.insert(&organization, &2);
+        let key = voucher::signer::public_key(&VOUCHER_SECRET);
+        prepare_env(ORGANIZATION);
+
+        contract.set_voucher_key(key.clone().into());
+
+        assert_eq!(contract.get_voucher_key(&organization).unwrap().0, key);
+        assert_eq!(contract.elections_count(&organization), "2");
+    }
+
+    #[test]
+    #[should_panic(expected = "Account is not registered")]
+    fn should_not_set_voucher_key_of_unregistered_organization() {
+        let mut contract = create_contract();
+        prepare_env(ORGANIZATION);
+
+        contract.set_voucher_key(voucher::signer::public_key(&VOUCHER_SECRET).into());
+    }
+
+    #[test]
+    fn should_migrate_legacy_state() {
+        prepare_env(OWNER);
+        let organization = account(ORGANIZATION);
+        let mut legacy = LegacyElections {
+            owner_id: account(OWNER),
+            organizations: LookupMap::new(StorageKeys::Organizations),
+            elections: LookupMap::new(StorageKeys::Elections),
+            votes: LookupMap::new(StorageKeys::Results),
+            voters: LookupSet::new(StorageKeys::Voters),
+        };
+        legacy.organizations.insert(&organization, &1);
+        legacy.elections.insert(
+            &(organization.clone(), 0),
+            &LegacyElection {
+                start: nanoseconds(Utc::now() - Duration::days(1)),
+                end: nanoseconds(Utc::now() + Duration::days(1)),
+                title: "Legacy".to_string(),
+                description: "Before settings".to_string(),
+                candidates: vec!["Alice".to_string(), "Bob".to_string()],
+            },
+        );
+        legacy.votes.insert(&(organization.clone(), 0, 0), &1);
+        legacy
+            .voters
+            .insert(&(organization.clone(), 0, account(USER)));
+        env::state_write(&legacy);
+
+        let mut contract = Elections::migrate();
+        contract.migrate_elections(&organization, vec!["0".to_string(), "0".to_string()]);
+
+        let election = contract.get_election(&organization, &"0".to_string());
+        assert_eq!(election.title, "Legacy");
+        assert_eq!(election.kind, ElectionKind::Plurality);
+        assert_eq!(election.candidates[0].votes, Some("1".to_string()));
+        assert_eq!(
+            contract.ballots_count.get(&(organization.clone(), 0)),
+            Some(1)
+        );
+        prepare_env(USER);
+        assert_eq!(
+            contract.have_voted(&organization, &"0".to_string()),
+            VoteStatus::Direct
+        );
+        prepare_env(OWNER);
+        contract.vote(&organization, &"0".to_string(), 1, None, None);
+        assert_eq!(contract.organizations.get(&organization), Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can migrate elections")]
+    fn should_not_migrate_elections_by_non_owner() {
+        let mut contract = create_contract();
+        prepare_env(USER);
+
+        contract.migrate_elections(&account(ORGANIZATION), vec!["0".to_string()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner")]
+    fn should_not_create_organization_by_non_owner() {
+        let mut contract = create_contract();
+        prepare_env(USER);
+        let organization = account(ORGANIZATION);
+
+        contract.register_organization(&organization, None);
+    }
+
+    #[test]
+    fn should_create_election() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        contract.organizations.insert(&organization, &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+        let input = ElectionInput::new();
+
+        let id = contract.create_election(&input).parse().unwrap();
+
+        assert_eq!(id, 0);
+        assert_eq!(contract.organizations.get(&organization).unwrap(), 1);
+        assert!(contract.elections.contains_key(&(organization.clone(), id)));
+        let saved = contract.elections.get(&(organization, id)).unwrap();
+        assert_eq!(saved.start.to_string(), input.start);
+        assert_eq!(saved.end.to_string(), input.end);
+        assert_eq!(saved.title, input.title);
+        assert_eq!(saved.description, input.description);
+        assert_eq!(saved.candidates, input.candidates);
+    }
+
+    #[test]
+    #[should_panic(expected = "not registered")]
+    fn should_check_organization_registration_on_create() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(USER)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(&ElectionInput::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "in the future")]
+    fn should_check_election_start_date_on_create() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        contract.organizations.insert(&organization, &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+        let input = ElectionInput::new()
+            .set_start(Utc::now().checked_sub_signed(Duration::days(1)).unwrap());
+
+        contract.create_election(&input);
+    }
+
+    #[test]
+    #[should_panic(expected = "before end")]
+    fn should_check_election_end_date_on_create() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        contract.organizations.insert(&organization, &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+        let input = ElectionInput::new().set_end(Utc::now());
+
+        contract.create_election(&input);
+    }
+
+    #[test]
+    #[should_panic(expected = "provided")]
+    fn should_check_that_candidates_are_provided() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        contract.organizations.insert(&organization, &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+        let input = ElectionInput::new().set_candidates(vec![]);
+
+        contract.create_election(&input);
+    }
+
+    #[test]
+    #[should_panic(expected = "provided")]
+    fn should_check_that_more_than_one_candidate_provided() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        contract.organizations.insert(&organization, &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+        let input = ElectionInput::new().set_candidates(vec!["Alice".to_string()]);
+
+        contract.create_election(&input);
+    }
+
+    #[test]
+    #[should_panic(expected = "paid")]
+    fn should_require_deposit_on_create() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        prepare_env(ORGANIZATION);
+
+        contract.create_election(&ElectionInput::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "paid")]
+    fn should_require_exact_deposit_on_create() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST * 2)
+            .build());
+
+        contract.create_election(&ElectionInput::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "256")]
+    fn should_allow_maximum_256_candidates() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(
+            &ElectionInput::new()
+                .set_candidates((0..).take(257).map(|_| "Bob".to_string()).collect()),
+        );
+    }
+
+    #[test]
+    fn should_fetch_election_count() {
+        let count = 14;
+        let organization = account(ORGANIZATION);
+        let mut contract = create_contract();
+        contract.organizations.insert(&organization, &count);
+        prepare_env(USER);
+
+        let result = contract.elections_count(&organization);
+
+        assert_eq!(result, count.to_string());
+    }
+
+    #[test]
+    fn should_return_true_if_voted() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let user = account(USER);
+        let election_id = 12;
+        contract
+            .voters
+            .insert(&(organization.clone(), election_id, user));
+        prepare_env(USER);
+
+        let result = contract.have_voted(&organization, &election_id.to_string());
+
+        assert_eq!(result, VoteStatus::Direct);
+    }
+
+    #[test]
+    fn should_return_false_if_not_voted() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 12;
+        prepare_env(USER);
+
+        let result = contract.have_voted(&organization, &election_id.to_string());
+
+        assert_eq!(result, VoteStatus::NotVoted);
+    }
+
+    #[test]
+    fn should_fetch_election() {
+        let organization = account(ORGANIZATION);
+        let mut contract = create_contract();
+        let election_id = 1;
+        let input = ElectionInput::new();
+        contract
+            .elections
+            .insert(&(organization.clone(), election_id), &Election::new(&input));
+        let bob_votes = 3;
+        contract
+            .votes
+            .insert(&(organization.clone(), election_id, 1), &bob_votes);
+        prepare_env(USER);
+
+        let result = contract.get_election(&organization, &election_id.to_string());
+
+        assert_eq!(result.start, input.start);
+        assert_eq!(result.end, input.end);
+        assert_eq!(result.title, input.title);
+        assert_eq!(result.description, input.description);
+        assert_eq!(result.candidates.len(), 2);
+        let alice = result.candidates.first().unwrap();
+        assert_eq!(alice.name, "Alice".to_string());
+        assert_eq!(alice.votes, Some("0".to_string()));
+        let bob = result.candidates.get(1).unwrap();
+        assert_eq!(bob.name, "Bob".to_string());
+        assert_eq!(bob.votes, Some(bob_votes.to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "started")]
+    fn should_panic_if_election_not_started() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        contract.elections.insert(
+            &(organization.clone(), election_id),
+            &Election::new(&ElectionInput::new()),
+        );
+        prepare_env(USER);
+
+        contract.vote(&organization, &election_id.to_string(), 0, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ended")]
+    fn should_panic_if_election_finished() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        contract.elections.insert(
+            &(organization.clone(), election_id),
+            &Election::new(&ElectionInput::new()),
+        );
+        testing_env!(context(USER)
+            .block_timestamp(nanoseconds(
+                Utc::now().checked_add_signed(Duration::days(4)).unwrap()
+            ))
+            .build());
+
+        contract.vote(&organization, &election_id.to_string(), 0, None, None);
+    }
+
+    #[test]
+    fn should_vote() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        contract.elections.insert(
+            &(organization.clone(), election_id),
+            &Election::new(&ElectionInput::new()),
+        );
+        testing_env!(context(USER)
+            .block_timestamp(nanoseconds(
+                Utc::now().checked_add_signed(Duration::days(2)).unwrap()
+            ))
+            .build());
+        let candidate_id = 1;
+
+        contract.vote(
+            &organization,
+            &election_id.to_string(),
+            candidate_id,
+            None,
+            None,
+        );
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, candidate_id))
+                .unwrap(),
+            1
+        );
+        assert!(contract
+            .voters
+            .contains(&(organization.clone(), election_id, account(USER))));
+    }
+
+    #[test]
+    fn should_change_vote() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        contract.elections.insert(
+            &(organization.clone(), election_id),
+            &Election::new(&ElectionInput::new()),
+        );
+        testing_env!(context(USER)
+            .block_timestamp(nanoseconds(
+                Utc::now().checked_add_signed(Duration::days(2)).unwrap()
+            ))
+            .build());
+
+        contract.vote(&organization, &election_id.to_string(), 0, None, None);
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 0))
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            contract
+                .ballots_count
+                .get(&(organization.clone(), election_id))
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn should_count_blank_ballots_separately() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_blank_options(vec![BlankOption::Abstain, BlankOption::Nota]),
+        );
+        prepare_env_at(USER, Duration::days(2));
+        contract.vote_blank(
+            &organization,
+            &election_id.to_string(),
+            BlankOption::Nota,
+            None,
+            None,
+        );
+        prepare_env_at(OWNER, Duration::days(2));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+        contract.vote_blank(
+            &organization,
+            &election_id.to_string(),
+            BlankOption::Abstain,
+            None,
+            None,
+        );
+
+        let view = contract.get_election(&organization, &election_id.to_string());
+
+        assert_eq!(view.abstentions, Some("1".to_string()));
+        assert_eq!(view.nota_votes, Some("1".to_string()));
+        assert_eq!(view.turnout, Some("2".to_string()));
+        assert_eq!(view.candidates[1].votes, Some("0".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Election doesn't accept Nota ballots")]
+    fn should_not_accept_disabled_blank_option() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_blank_options(vec![BlankOption::Abstain]),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_blank(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            BlankOption::Nota,
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    fn should_hide_blank_votes_of_election_without_them() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(&mut contract, election_id, ElectionInput::new());
+
+        let view = contract.get_election(&organization, &election_id.to_string());
+
+        assert_eq!(view.abstentions, None);
+        assert_eq!(view.nota_votes, None);
+    }
+
+    #[test]
+    fn should_retract_vote() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(&mut contract, election_id, ElectionInput::new());
+        prepare_env_at(USER, Duration::days(2));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+
+        contract.retract_vote(&organization, &election_id.to_string());
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            contract
+                .ballots_count
+                .get(&(organization.clone(), election_id))
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            contract.have_voted(&organization, &election_id.to_string()),
+            VoteStatus::NotVoted
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "No ballot to retract")]
+    fn should_not_retract_missing_vote() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(&mut contract, election_id, ElectionInput::new());
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.retract_vote(&account(ORGANIZATION), &election_id.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Election already ended")]
+    fn should_not_retract_vote_after_end() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(&mut contract, election_id, ElectionInput::new());
+        prepare_env_at(USER, Duration::days(2));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.retract_vote(&organization, &election_id.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "single-choice ballots")]
+    fn should_prohibit_single_vote_in_ranked_election() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::InstantRunoff),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote(&organization, &election_id.to_string(), 0, None, None);
+    }
+
+    #[test]
+    fn should_vote_ranked() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::InstantRunoff),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_ranked(&organization, &election_id.to_string(), vec![1, 0]);
+
+        let tally = contract
+            .rankings
+            .get(&(organization.clone(), election_id, 0))
+            .unwrap();
+        assert_eq!(tally.ranking, vec![1, 0]);
+        assert_eq!(tally.ballots, 1);
+        assert_eq!(
+            contract
+                .ballots_count
+                .get(&(organization.clone(), election_id))
+                .unwrap(),
+            1
+        );
+        assert!(contract
+            .voters
+            .contains(&(organization, election_id, account(USER))));
+    }
+
+    #[test]
+    fn should_group_identical_rankings() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::InstantRunoff),
+        );
+
+        for (voter, ranking) in [
+            (USER, vec![1, 0]),
+            (OWNER, vec![0]),
+            ("carol.testnet", vec![1, 0]),
+        ] {
+            prepare_env_at(voter, Duration::days(2));
+            contract.vote_ranked(&organization, &election_id.to_string(), ranking);
+        }
+
+        let key = (organization, election_id);
+        assert_eq!(
+            contract.stored_rankings(&key),
+            vec![(vec![1, 0], 2), (vec![0], 1)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "more than once")]
+    fn should_prohibit_duplicates_in_ranking() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::InstantRunoff),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_ranked(&account(ORGANIZATION), &election_id.to_string(), vec![1, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not found")]
+    fn should_prohibit_unknown_candidates_in_ranking() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::InstantRunoff),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_ranked(&account(ORGANIZATION), &election_id.to_string(), vec![0, 2]);
+    }
+
+    #[test]
+    fn should_vote_approval() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Approval {
+                min_choices: None,
+                max_choices: None,
+            }),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_approval(&organization, &election_id.to_string(), vec![0, 1]);
+
+        for candidate_id in 0..2 {
+            assert_eq!(
+                contract
+                    .votes
+                    .get(&(organization.clone(), election_id, candidate_id))
+                    .unwrap(),
+                1
+            );
+        }
+        assert!(contract
+            .voters
+            .contains(&(organization, election_id, account(USER))));
+    }
+
+    #[test]
+    #[should_panic(expected = "already voted")]
+    fn should_prohibit_to_vote_approval_twice() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Approval {
+                min_choices: None,
+                max_choices: None,
+            }),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_approval(&organization, &election_id.to_string(), vec![0]);
+        contract.vote_approval(&organization, &election_id.to_string(), vec![1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected from 1 to 1 candidates")]
+    fn should_check_approval_max_choices() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Approval {
+                min_choices: None,
+                max_choices: Some(1),
+            }),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_approval(&account(ORGANIZATION), &election_id.to_string(), vec![0, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected from 2 to 2 candidates")]
+    fn should_check_approval_min_choices() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Approval {
+                min_choices: Some(2),
+                max_choices: None,
+            }),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_approval(&account(ORGANIZATION), &election_id.to_string(), vec![0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceed candidates count")]
+    fn should_check_approval_limits_on_create() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(&ElectionInput::new().set_kind(ElectionKind::Approval {
+            min_choices: None,
+            max_choices: Some(3),
+        }));
+    }
+
+    #[test]
+    fn should_vote_scores() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Score { max_score: 5 }),
+        );
+        for (voter, scores) in [("u1.testnet", vec![5, 1]), ("u2.testnet", vec![3, 2])] {
+            prepare_env_at(voter, Duration::days(2));
+            contract.vote_scores(&organization, &election_id.to_string(), scores);
+        }
+
+        let result = contract.get_election(&organization, &election_id.to_string());
+
+        let alice = result.candidates.first().unwrap();
+        assert_eq!(alice.votes, Some("2".to_string()));
+        assert_eq!(alice.total_score, Some("8".to_string()));
+        assert_eq!(alice.average_score, Some("4.00".to_string()));
+        let bob = result.candidates.get(1).unwrap();
+        assert_eq!(bob.total_score, Some("3".to_string()));
+        assert_eq!(bob.average_score, Some("1.50".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "between 0 and 5")]
+    fn should_check_score_scale() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Score { max_score: 5 }),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_scores(&account(ORGANIZATION), &election_id.to_string(), vec![6, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Every candidate should be scored")]
+    fn should_require_score_for_every_candidate() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Score { max_score: 5 }),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_scores(&account(ORGANIZATION), &election_id.to_string(), vec![4]);
+    }
+
+    #[test]
+    fn should_average_scores_with_rounding() {
+        let tally = ScoreTally {
+            total: 11,
+            ballots: 3,
+        };
+
+        assert_eq!(tally.average(), "3.67");
+    }
+
+    #[test]
+    fn should_vote_quadratic() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Quadratic { credits: 10 }),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_quadratic(
+            &organization,
+            &election_id.to_string(),
+            vec![(0, 3), (1, 1)],
+        );
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 0))
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            1
+        );
+        assert!(contract
+            .voters
+            .contains(&(organization, election_id, account(USER))));
+    }
+
+    #[test]
+    #[should_panic(expected = "costs 13 credits, only 10 available")]
+    fn should_reject_over_budget_quadratic_ballot() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Quadratic { credits: 10 }),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_quadratic(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            vec![(0, 3), (1, 2)],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "more than once")]
+    fn should_prohibit_splitting_quadratic_allocation() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Quadratic { credits: 10 }),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_quadratic(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            vec![(0, 2), (0, 2)],
+        );
+    }
+
+    #[test]
+    fn should_track_pairwise_preferences() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_kind(ElectionKind::Schulze)
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ]),
+        );
+        for (voter, ranking) in [
+            ("u1.testnet", vec![0, 1, 2]),
+            ("u2.testnet", vec![1, 0]),
+            ("u3.testnet", vec![0, 2]),
+        ] {
+            prepare_env_at(voter, Duration::days(2));
+            contract.vote_ranked(&organization, &election_id.to_string(), ranking);
+        }
+
+        let result = contract.get_pairwise(&organization, &election_id.to_string());
+
+        assert_eq!(
+            result.matrix,
+            vec![
+                vec!["0", "2", "3"],
+                vec!["1", "0", "2"],
+                vec!["0", "1", "0"]
+            ]
+        );
+        assert_eq!(result.condorcet_winner, Some(0));
+        assert_eq!(result.schulze_winners, vec![0]);
+        assert_eq!(
+            result.strongest_paths,
+            vec![
+                vec!["0", "2", "3"],
+                vec!["0", "0", "2"],
+                vec!["0", "0", "0"]
+            ]
+        );
+        assert!(!contract
+            .rankings
+            .contains_key(&(organization, election_id, 0)));
+    }
+
+    #[test]
+    fn should_finalize_schulze_election() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Schulze),
+        );
+        prepare_env_at(USER, Duration::days(2));
+        contract.vote_ranked(&organization, &election_id.to_string(), vec![1]);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.winner, Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "only for Schulze")]
+    fn should_not_return_pairwise_preferences_for_other_elections() {
+        let mut contract = create_contract();
+        insert_election(&mut contract, 1, ElectionInput::new());
+        prepare_env(USER);
+
+        contract.get_pairwise(&account(ORGANIZATION), &"1".to_string());
+    }
+
+    #[test]
+    fn should_finalize_top_k_plurality_election() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_seats(2).set_candidates(vec![
+                "Alice".to_string(),
+                "Bob".to_string(),
+                "Carol".to_string(),
+            ]),
+        );
+        for (candidate_id, votes) in [(0, 2), (1, 5), (2, 3)] {
+            contract
+                .votes
+                .insert(&(organization.clone(), election_id, candidate_id), &votes);
+        }
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.elected, vec![1, 2]);
+        assert_eq!(result.winner, Some(1));
+    }
+
+    #[test]
+    fn should_not_elect_without_quorum() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_quorum(QuorumInput::Ballots("3".to_string())),
+        );
+        insert_plain_tallies(&mut contract, election_id, &[2, 0]);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.outcome, Outcome::NoQuorum);
+        assert!(result.elected.is_empty());
+        assert_eq!(result.turnout, "2");
+        assert_eq!(result.required_turnout, Some("3".to_string()));
+    }
+
+    #[test]
+    fn should_elect_with_allowlist_quorum() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_allowlist()
+                .set_quorum(QuorumInput::AllowlistPercent(50)),
+        );
+        contract
+            .allowlist_sizes
+            .insert(&(organization.clone(), election_id), &3);
+        insert_plain_tallies(&mut contract, election_id, &[0, 2]);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.outcome, Outcome::Elected);
+        assert_eq!(result.winner, Some(1));
+        assert_eq!(result.required_turnout, Some("2".to_string()));
+    }
+
+    #[test]
+    fn should_not_elect_without_supermajority() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_majority(Majority::TwoThirds),
+        );
+        insert_plain_tallies(&mut contract, election_id, &[3, 2]);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.outcome, Outcome::ThresholdNotMet);
+        assert_eq!(result.winner, None);
+    }
+
+    #[test]
+    fn should_report_tie() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(&mut contract, election_id, ElectionInput::new());
+        insert_plain_tallies(&mut contract, election_id, &[2, 2]);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.outcome, Outcome::Tie);
+        assert!(result.elected.is_empty());
+        assert!(result.tie_break.is_none());
+    }
+
+    #[test]
+    fn should_break_tie_with_random_seed() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_tie_break(TieBreak::Random)
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ]),
+        );
+        insert_plain_tallies(&mut contract, election_id, &[1, 2, 2]);
+        let seed = vec![7; 32];
+        testing_env!(context(USER)
+            .block_timestamp(nanoseconds(
+                Utc::now().checked_add_signed(Duration::days(4)).unwrap()
+            ))
+            .random_seed(seed.clone())
+            .build());
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        let hash = |candidate: u8| env::sha256(&[&seed[..], &[candidate]].concat());
+        let expected = if hash(1) < hash(2) { 1 } else { 2 };
+        assert_eq!(result.outcome, Outcome::Elected);
+        assert_eq!(result.winner, Some(expected));
+        let tie_break = result.tie_break.unwrap();
+        assert_eq!(tie_break.policy, TieBreak::Random);
+        assert_eq!(tie_break.tied, vec![1, 2]);
+        assert_eq!(tie_break.chosen, vec![expected]);
+        assert_eq!(tie_break.seed.unwrap().0, seed);
+    }
+
+    #[test]
+    fn should_break_tie_by_first_vote() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_tie_break(TieBreak::FirstVote),
+        );
+        prepare_env_at(OWNER, Duration::hours(30));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+        prepare_env_at(USER, Duration::hours(40));
+        contract.vote(&organization, &election_id.to_string(), 0, None, None);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.outcome, Outcome::Elected);
+        assert_eq!(result.winner, Some(1));
+        assert!(result.tie_break.unwrap().seed.is_none());
+    }
+
+    #[test]
+    fn should_break_tie_by_first_vote_still_counted() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_tie_break(TieBreak::FirstVote),
+        );
+        prepare_env_at(OWNER, Duration::hours(26));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+        prepare_env_at(USER, Duration::hours(30));
+        contract.vote(&organization, &election_id.to_string(), 0, None, None);
+        prepare_env_at(OWNER, Duration::hours(34));
+        contract.retract_vote(&organization, &election_id.to_string());
+        prepare_env_at(ORGANIZATION, Duration::hours(36));
+        contract.vote(&organization, &election_id.to_string(), 0, None, None);
+        prepare_env_at(ORGANIZATION, Duration::hours(38));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.tie_break.unwrap().tied, vec![0, 1]);
+        assert_eq!(result.winner, Some(0));
+    }
+
+    #[test]
+    fn should_relink_first_votes_retracted_out_of_order() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_tie_break(TieBreak::FirstVote),
+        );
+        for (voter, hours) in [(OWNER, 26), (USER, 28), (ORGANIZATION, 30)] {
+            prepare_env_at(voter, Duration::hours(hours));
+            contract.vote(&organization, &election_id.to_string(), 0, None, None);
+        }
+        let last_vote = env::block_timestamp();
+        prepare_env_at(USER, Duration::hours(32));
+        contract.retract_vote(&organization, &election_id.to_string());
+        prepare_env_at(OWNER, Duration::hours(34));
+        contract.retract_vote(&organization, &election_id.to_string());
+
+        assert_eq!(
+            contract.first_vote(&(organization, election_id), 0),
+            Some(last_vote)
+        );
+    }
+
+    #[test]
+    fn should_not_list_ballots_without_first_vote_tie_break() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_tie_break(TieBreak::Random),
+        );
+        prepare_env_at(OWNER, Duration::hours(30));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+
+        assert!(contract
+            .first_votes
+            .get(&(organization, election_id, 1))
+            .is_none());
+    }
+
+    #[test]
+    fn should_break_tie_by_candidate_order() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_tie_break(TieBreak::CandidateOrder)
+                .set_seats(2)
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                    "Dave".to_string(),
+                ]),
+        );
+        insert_plain_tallies(&mut contract, election_id, &[1, 2, 3, 2]);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.outcome, Outcome::Elected);
+        assert_eq!(result.elected, vec![2, 1]);
+        assert_eq!(result.tie_break.unwrap().tied, vec![1, 3]);
+    }
+
+    #[test]
+    fn should_let_organization_break_tie() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_tie_break(TieBreak::Organization),
+        );
+        insert_plain_tallies(&mut contract, election_id, &[2, 2]);
+        prepare_env_at(USER, Duration::days(4));
+        contract.finalize_election(&organization, &election_id.to_string());
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.outcome, Outcome::Tie);
+        assert!(result.tie_break.unwrap().chosen.is_empty());
+
+        prepare_env_at(ORGANIZATION, Duration::days(4));
+        contract.break_tie(&election_id.to_string(), vec![1]);
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.outcome, Outcome::Elected);
+        assert_eq!(result.winner, Some(1));
+        assert_eq!(result.tie_break.unwrap().chosen, vec![1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Candidate 2 is not tied")]
+    fn should_break_tie_only_with_tied_candidates() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_tie_break(TieBreak::Organization)
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ]),
+        );
+        insert_plain_tallies(&mut contract, election_id, &[2, 2, 1]);
+        prepare_env_at(USER, Duration::days(4));
+        contract.finalize_election(&account(ORGANIZATION), &election_id.to_string());
+        prepare_env_at(ORGANIZATION, Duration::days(4));
+
+        contract.break_tie(&election_id.to_string(), vec![2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Election has no tie to break")]
+    fn should_not_break_tie_of_decided_election() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_tie_break(TieBreak::Organization),
+        );
+        insert_plain_tallies(&mut contract, election_id, &[2, 1]);
+        prepare_env_at(USER, Duration::days(4));
+        contract.finalize_election(&account(ORGANIZATION), &election_id.to_string());
+        prepare_env_at(ORGANIZATION, Duration::days(4));
+
+        contract.break_tie(&election_id.to_string(), vec![1]);
+    }
+
+    #[test]
+    fn should_not_meet_majority_with_tie() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_majority(Majority::Simple)
+                .set_tie_break(TieBreak::CandidateOrder),
+        );
+        insert_plain_tallies(&mut contract, election_id, &[2, 2]);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.outcome, Outcome::ThresholdNotMet);
+        assert!(result.tie_break.is_none());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Only random and candidate order tie-breaks are supported by instant runoff"
+    )]
+    fn should_not_allow_organization_tie_break_in_instant_runoff() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(
+            &ElectionInput::new()
+                .set_kind(ElectionKind::InstantRunoff)
+                .set_tie_break(TieBreak::Organization),
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Election could have only one of Merkle allowlist, token gate or NFT gate"
+    )]
+    fn should_not_allow_several_gates() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(
+            &ElectionInput::new()
+                .set_token_gate(10, TokenWeighting::Balance)
+                .set_nft_contract(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "NFT-gated voting is supported only by plurality elections")]
+    fn should_not_allow_gate_without_plain_account_ballots() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(&ElectionInput::new().set_encryption(3, 2).set_nft_contract());
+    }
+
+    #[test]
+    #[should_panic(expected = "Majority threshold is supported only by single seat")]
+    fn should_allow_majority_threshold_only_for_single_seat() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(
+            &ElectionInput::new()
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ])
+                .set_seats(2)
+                .set_majority(Majority::Simple),
+        );
+    }
+
+    /// Stores plurality tallies of ballots cast by accounts, one ballot per vote.
+    fn insert_plain_tallies(contract: &mut Elections, election_id: ElectionId, votes: &[u128]) {
+        let organization = account(ORGANIZATION);
+        for (candidate_id, votes) in votes.iter().enumerate() {
+            contract.votes.insert(
+                &(
+                    organization.clone(),
+                    election_id,
+                    candidate_id.try_into().unwrap(),
+                ),
+                votes,
+            );
+        }
+        contract
+            .ballots_count
+            .insert(&(organization, election_id), &votes.iter().sum());
+    }
+
+    #[test]
+    fn should_finalize_stv_election() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_kind(ElectionKind::Stv)
+                .set_seats(2)
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ]),
+        );
+        let ballots = [
+            ("u1.testnet", vec![0, 1]),
+            ("u2.testnet", vec![0, 1]),
+            ("u3.testnet", vec![0, 1]),
+            ("u4.testnet", vec![0]),
+            ("u5.testnet", vec![2]),
+        ];
+        for (voter, ranking) in ballots {
+            prepare_env_at(voter, Duration::days(2));
+            contract.vote_ranked(&organization, &election_id.to_string(), ranking);
+        }
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.quota, Some("2".to_string()));
+        assert_eq!(result.elected, vec![0, 1]);
+        let transfer = result.rounds.get(1).unwrap();
+        assert_eq!(transfer.tallies.first().unwrap().votes, "1.500000");
+        assert_eq!(transfer.exhausted, "0.500000");
+    }
+
+    #[test]
+    #[should_panic(expected = "Maximum 32 candidates expected in Schulze elections")]
+    fn should_limit_candidates_of_schulze_election() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(
+            &ElectionInput::new()
+                .set_kind(ElectionKind::Schulze)
+                .set_candidates((0..33).map(|i| format!("Candidate {}", i)).collect()),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Maximum 20 candidates expected in instant runoff and STV elections")]
+    fn should_limit_candidates_of_instant_runoff_election() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(
+            &ElectionInput::new()
+                .set_kind(ElectionKind::InstantRunoff)
+                .set_candidates((0..21).map(|i| format!("Candidate {}", i)).collect()),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Maximum 20 candidates expected in instant runoff and STV elections")]
+    fn should_limit_candidates_of_stv_election() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(
+            &ElectionInput::new()
+                .set_kind(ElectionKind::Stv)
+                .set_seats(2)
+                .set_candidates((0..21).map(|i| format!("Candidate {}", i)).collect()),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "only a single seat")]
+    fn should_not_allow_several_seats_in_instant_runoff() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(
+            &ElectionInput::new()
+                .set_kind(ElectionKind::InstantRunoff)
+                .set_seats(2)
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ]),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "fewer than candidates")]
+    fn should_require_fewer_seats_than_candidates() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(&ElectionInput::new().set_seats(2));
+    }
+
+    #[test]
+    fn should_leave_tied_seats_unfilled() {
+        assert_eq!(top_candidates(&[3, 2, 2], 2), vec![0]);
+        assert_eq!(top_candidates(&[3, 2, 2], 1), vec![0]);
+        assert_eq!(top_candidates(&[2, 2], 1), Vec::<CandidateId>::new());
+    }
+
+    #[test]
+    fn should_find_tied_candidates() {
+        let tallies = [2, 3, 2, 1];
+        let tied = |seats| tied_candidates_by(4, seats, |a, b| tallies[a].cmp(&tallies[b]));
+        assert_eq!(tied(1), vec![0, 2]);
+        assert_eq!(tied(2), vec![0, 2]);
+        assert_eq!(tied(3), vec![3]);
+    }
+
+    #[test]
+    fn should_award_borda_points() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_kind(ElectionKind::Positional {
+                    rule: PositionalRule::Borda,
+                })
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ]),
+        );
+        for (voter, ranking) in [("u1.testnet", vec![0, 1, 2]), ("u2.testnet", vec![1, 2])] {
+            prepare_env_at(voter, Duration::days(2));
+            contract.vote_ranked(&organization, &election_id.to_string(), ranking);
+        }
+
+        let result = contract.get_election(&organization, &election_id.to_string());
+
+        let points: Vec<&str> = result
+            .candidates
+            .iter()
+            .map(|c| c.votes.as_deref().unwrap())
+            .collect();
+        assert_eq!(points, vec!["2", "3", "1"]);
+    }
+
+    #[test]
+    fn should_award_dowdall_points() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_kind(ElectionKind::Positional {
+                    rule: PositionalRule::Dowdall,
+                })
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ]),
+        );
+        for (voter, ranking) in [("u1.testnet", vec![0, 1, 2]), ("u2.testnet", vec![1, 2])] {
+            prepare_env_at(voter, Duration::days(2));
+            contract.vote_ranked(&organization, &election_id.to_string(), ranking);
+        }
+
+        let result = contract.get_election(&organization, &election_id.to_string());
+
+        let points: Vec<&str> = result
+            .candidates
+            .iter()
+            .map(|c| c.votes.as_deref().unwrap())
+            .collect();
+        assert_eq!(points, vec!["1.000000", "1.500000", "0.833333"]);
+    }
+
+    #[test]
+    fn should_count_dowdall_points_exactly() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_kind(ElectionKind::Positional {
+                    rule: PositionalRule::Dowdall,
+                })
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                    "Dave".to_string(),
+                ]),
+        );
+        for (voter, ranking) in [
+            ("u1.testnet", vec![0]),
+            ("u2.testnet", vec![3, 1, 2]),
+            ("u3.testnet", vec![3, 1, 2]),
+            ("u4.testnet", vec![3, 1, 2]),
+        ] {
+            prepare_env_at(voter, Duration::days(2));
+            contract.vote_ranked(&organization, &election_id.to_string(), ranking);
+        }
+
+        let points = |candidate_id| {
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, candidate_id))
+        };
+        assert_eq!(points(0), points(2));
+        let result = contract.get_election(&organization, &election_id.to_string());
+        assert_eq!(result.candidates[2].votes, Some("1.000000".to_string()));
+    }
+
+    #[test]
+    fn should_award_custom_points() {
+        let rule = PositionalRule::Custom(vec![5, 2]);
+
+        assert_eq!(rule.points(0, 3), 5);
+        assert_eq!(rule.points(1, 3), 2);
+        assert_eq!(rule.points(2, 3), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "should not increase")]
+    fn should_check_custom_points_order_on_create() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(&ElectionInput::new().set_kind(ElectionKind::Positional {
+            rule: PositionalRule::Custom(vec![1, 2]),
+        }));
+    }
+
+    #[test]
+    fn should_vote_cumulative() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Cumulative { points: 10 }),
+        );
+        for (voter, allocations) in [
+            ("u1.testnet", vec![(1, 10)]),
+            ("u2.testnet", vec![(0, 4), (1, 5)]),
+        ] {
+            prepare_env_at(voter, Duration::days(2));
+            contract.vote_cumulative(&organization, &election_id.to_string(), allocations);
+        }
+
+        let result = contract.get_election(&organization, &election_id.to_string());
+
+        assert_eq!(
+            result.candidates.first().unwrap().votes,
+            Some("4".to_string())
+        );
+        assert_eq!(
+            result.candidates.get(1).unwrap().votes,
+            Some("15".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "distributes 11 points, only 10 available")]
+    fn should_reject_over_budget_cumulative_ballot() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Cumulative { points: 10 }),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_cumulative(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            vec![(0, 6), (1, 5)],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't accept cumulative ballots")]
+    fn should_not_accept_cumulative_ballot_in_quadratic_election() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(ElectionKind::Quadratic { credits: 10 }),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_cumulative(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            vec![(0, 6)],
+        );
+    }
+
+    #[test]
+    fn should_create_runoff_without_majority() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        contract.organizations.insert(&organization, &2);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_runoff(None, Duration::days(2))
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ]),
+        );
+        for (candidate_id, votes) in [(0, 3), (1, 2), (2, 4)] {
+            contract
+                .votes
+                .insert(&(organization.clone(), election_id, candidate_id), &votes);
+        }
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert!(result.elected.is_empty());
+        assert_eq!(result.runoff_id, Some("2".to_string()));
+        assert_eq!(contract.organizations.get(&organization).unwrap(), 3);
+        let parent = contract.get_election(&organization, &election_id.to_string());
+        assert_eq!(parent.runoff_id, Some("2".to_string()));
+        let runoff = contract.get_election(&organization, &"2".to_string());
+        assert_eq!(runoff.parent_id, Some(election_id.to_string()));
+        assert_eq!(runoff.title, "My Election (runoff)");
+        let names: Vec<&str> = runoff.candidates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Carol", "Alice"]);
+        assert!(result.tie_break.is_none());
+    }
+
+    #[test]
+    fn should_keep_reveal_phase_in_runoff_of_secret_election() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        contract.organizations.insert(&organization, &2);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            secret_election()
+                .set_runoff(None, Duration::days(2))
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ]),
+        );
+        let parent = contract
+            .elections
+            .get(&(organization.clone(), election_id))
+            .unwrap();
+        for (candidate_id, votes) in [(0, 3), (1, 2), (2, 4)] {
+            contract
+                .votes
+                .insert(&(organization.clone(), election_id, candidate_id), &votes);
+        }
+        prepare_env_at(USER, Duration::days(6));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let runoff = contract.elections.get(&(organization, 2)).unwrap();
+        assert_eq!(
+            runoff.reveal_end.unwrap() - runoff.end,
+            parent.reveal_end.unwrap() - parent.end
+        );
+    }
+
+    #[test]
+    fn should_advance_all_tied_candidates_to_runoff() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        contract.organizations.insert(&organization, &2);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_runoff(None, Duration::days(2))
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ]),
+        );
+        insert_plain_tallies(&mut contract, election_id, &[2, 2, 4]);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        let tie_break = result.tie_break.unwrap();
+        assert_eq!(tie_break.tied, vec![0, 1]);
+        assert_eq!(tie_break.chosen, vec![0, 1]);
+        let runoff = contract.get_election(&organization, &"2".to_string());
+        let names: Vec<&str> = runoff.candidates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Carol", "Alice", "Bob"]);
+    }
+
+    #[test]
+    fn should_break_runoff_tie_with_election_policy() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        contract.organizations.insert(&organization, &2);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_runoff(None, Duration::days(2))
+                .set_tie_break(TieBreak::CandidateOrder)
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ]),
+        );
+        insert_plain_tallies(&mut contract, election_id, &[2, 4, 2]);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.outcome, Outcome::Runoff);
+        let tie_break = result.tie_break.unwrap();
+        assert_eq!(tie_break.tied, vec![0, 2]);
+        assert_eq!(tie_break.chosen, vec![0]);
+        let runoff = contract.get_election(&organization, &"2".to_string());
+        let names: Vec<&str> = runoff.candidates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Bob", "Alice"]);
+    }
+
+    #[test]
+    fn should_not_create_runoff_with_majority() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        contract.organizations.insert(&organization, &2);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_runoff(Some(60), Duration::days(2)),
+        );
+        for (candidate_id, votes) in [(0, 7), (1, 3)] {
+            contract
+                .votes
+                .insert(&(organization.clone(), election_id, candidate_id), &votes);
+        }
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.elected, vec![0]);
+        assert_eq!(result.runoff_id, None);
+        assert_eq!(contract.organizations.get(&organization).unwrap(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "single seat plurality")]
+    fn should_allow_runoff_only_for_plurality() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+
+        contract.create_election(
+            &ElectionInput::new()
+                .set_kind(ElectionKind::InstantRunoff)
+                .set_runoff(None, Duration::days(2)),
+        );
+    }
+
+    #[test]
+    fn should_vote_grades() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(grades_kind()),
+        );
+        for (voter, grades) in [("u1.testnet", vec![2, 0]), ("u2.testnet", vec![2, 1])] {
+            prepare_env_at(voter, Duration::days(2));
+            contract.vote_grades(&organization, &election_id.to_string(), grades);
+        }
+
+        let result = contract.get_election(&organization, &election_id.to_string());
+
+        let alice = result.candidates.first().unwrap();
+        assert_eq!(alice.votes, Some("2".to_string()));
+        assert_eq!(
+            alice.histogram,
+            Some(vec!["0".to_string(), "0".to_string(), "2".to_string()])
+        );
+        let bob = result.candidates.get(1).unwrap();
+        assert_eq!(
+            bob.histogram,
+            Some(vec!["1".to_string(), "1".to_string(), "0".to_string()])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "between 0 and 2")]
+    fn should_check_grade_labels() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(grades_kind()),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_grades(&account(ORGANIZATION), &election_id.to_string(), vec![3, 0]);
+    }
+
+    #[test]
+    fn should_finalize_majority_judgment_election() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_kind(grades_kind()),
+        );
+        contract
+            .grades
+            .insert(&(organization.clone(), election_id, 0), &vec![1, 2, 1]);
+        contract
+            .grades
+            .insert(&(organization.clone(), election_id, 1), &vec![0, 3, 1]);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.elected, vec![1]);
+        assert_eq!(
+            result.median_grades,
+            Some(vec!["Fair".to_string(), "Fair".to_string()])
+        );
+    }
+
+    #[test]
+    fn should_commit_and_reveal_vote() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(&mut contract, election_id, secret_election());
+        let salt = b"pepper".to_vec();
+        prepare_env_at(USER, Duration::days(2));
+        contract.commit_vote(
+            &organization,
+            &election_id.to_string(),
+            commitment(1, &salt),
+        );
+
+        assert_eq!(
+            contract.have_voted(&organization, &election_id.to_string()),
+            VoteStatus::Direct
+        );
+        assert_eq!(
+            contract.votes.get(&(organization.clone(), election_id, 1)),
+            None
+        );
+        let unrevealed = contract.get_unrevealed_commitments(
+            &organization,
+            &election_id.to_string(),
+            &"0".to_string(),
+            10,
+        );
+        assert_eq!(unrevealed.len(), 1);
+        assert_eq!(unrevealed.first().unwrap().voter, account(USER));
+
+        prepare_env_at(USER, Duration::days(4));
+        contract.reveal_vote(&organization, &election_id.to_string(), 1, salt.into());
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            1
+        );
+        assert!(contract
+            .get_unrevealed_commitments(
+                &organization,
+                &election_id.to_string(),
+                &"0".to_string(),
+                10
+            )
+            .is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't match")]
+    fn should_reject_reveal_not_matching_commitment() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(&mut contract, election_id, secret_election());
+        prepare_env_at(USER, Duration::days(2));
+        contract.commit_vote(
+            &organization,
+            &election_id.to_string(),
+            commitment(1, b"pepper"),
+        );
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.reveal_vote(
+            &organization,
+            &election_id.to_string(),
+            0,
+            b"pepper".to_vec().into(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Reveal phase not started")]
+    fn should_not_reveal_during_voting() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(&mut contract, election_id, secret_election());
+        prepare_env_at(USER, Duration::days(2));
+        contract.commit_vote(
+            &organization,
+            &election_id.to_string(),
+            commitment(1, b"pepper"),
+        );
+
+        contract.reveal_vote(
+            &organization,
+            &election_id.to_string(),
+            1,
+            b"pepper".to_vec().into(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "accepts only committed ballots")]
+    fn should_not_accept_plain_vote_in_secret_election() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(&mut contract, election_id, secret_election());
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "not ended")]
+    fn should_not_finalize_secret_election_during_reveal() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(&mut contract, election_id, secret_election());
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&account(ORGANIZATION), &election_id.to_string());
+    }
+
+    #[test]
+    fn should_count_only_revealed_ballots_toward_quorum() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            secret_election().set_quorum(QuorumInput::Ballots("2".to_string())),
+        );
+        let salt = b"pepper".to_vec();
+        for voter in [USER, OWNER] {
+            prepare_env_at(voter, Duration::days(2));
+            contract.commit_vote(
+                &organization,
+                &election_id.to_string(),
+                commitment(1, &salt),
+            );
+        }
+        prepare_env_at(USER, Duration::days(4));
+        contract.reveal_vote(&organization, &election_id.to_string(), 1, salt.into());
+        prepare_env_at(USER, Duration::days(6));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.outcome, Outcome::NoQuorum);
+        assert_eq!(result.turnout, "1");
+        let election = contract.get_election(&organization, &election_id.to_string());
+        assert_eq!(election.turnout, Some("1".to_string()));
+    }
+
+    #[test]
+    fn should_hide_counts_until_election_end() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_results_visibility(ResultsVisibility::AfterEnd, false),
+        );
+        prepare_env_at(USER, Duration::days(2));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+
+        let running = contract.get_election(&organization, &election_id.to_string());
+        prepare_env_at(USER, Duration::days(4));
+        let ended = contract.get_election(&organization, &election_id.to_string());
+
+        assert!(running.candidates.iter().all(|c| c.votes.is_none()));
+        assert_eq!(running.turnout, None);
+        assert_eq!(
+            ended.candidates.get(1).unwrap().votes,
+            Some("1".to_string())
+        );
+        assert_eq!(ended.turnout, Some("1".to_string()));
+    }
+
+    #[test]
+    fn should_show_turnout_of_hidden_election_if_opted_in() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_results_visibility(ResultsVisibility::AfterEnd, true),
+        );
+        prepare_env_at(USER, Duration::days(2));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+
+        let result = contract.get_election(&organization, &election_id.to_string());
+
+        assert!(result.candidates.iter().all(|c| c.votes.is_none()));
+        assert_eq!(result.turnout, Some("1".to_string()));
+    }
+
+    #[test]
+    fn should_show_counts_once_published() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_results_visibility(ResultsVisibility::AfterPublish, false),
+        );
+        prepare_env_at(USER, Duration::days(4));
+        contract.finalize_election(&organization, &election_id.to_string());
+        let hidden = contract.get_election(&organization, &election_id.to_string());
+
+        prepare_env_at(ORGANIZATION, Duration::days(4));
+        contract.publish_results(&election_id.to_string());
+
+        let published = contract.get_election(&organization, &election_id.to_string());
+        assert!(hidden.candidates.iter().all(|c| c.votes.is_none()));
+        assert_eq!(
+            published.candidates.first().unwrap().votes,
+            Some("0".to_string())
+        );
+        contract.get_results(&organization, &election_id.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "not visible yet")]
+    fn should_hide_final_results_until_published() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_results_visibility(ResultsVisibility::AfterPublish, false),
+        );
+        prepare_env_at(USER, Duration::days(4));
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        contract.get_results(&organization, &election_id.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "not ended")]
+    fn should_not_publish_running_election() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_results_visibility(ResultsVisibility::AfterPublish, false),
+        );
+        prepare_env_at(ORGANIZATION, Duration::days(2));
+
+        contract.publish_results(&election_id.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "not ended")]
+    fn should_not_finalize_running_election() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(&mut contract, election_id, ElectionInput::new());
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.finalize_election(&account(ORGANIZATION), &election_id.to_string());
+    }
+
+    #[test]
+    fn should_finalize_plurality_election() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(&mut contract, election_id, ElectionInput::new());
+        contract
+            .votes
+            .insert(&(organization.clone(), election_id, 1), &3);
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.winner, Some(1));
+        assert!(result.rounds.is_empty());
+    }
+
+    #[test]
+    fn should_finalize_instant_runoff_election() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_kind(ElectionKind::InstantRunoff)
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ]),
+        );
+        let ballots = [
+            ("u1.testnet", vec![0]),
+            ("u2.testnet", vec![0]),
+            ("u3.testnet", vec![1, 2]),
+            ("u4.testnet", vec![1]),
+            ("u5.testnet", vec![2, 1]),
+        ];
+        for (voter, ranking) in ballots {
+            prepare_env_at(voter, Duration::days(2));
+            contract.vote_ranked(&organization, &election_id.to_string(), ranking);
+        }
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.winner, Some(1));
+        assert_eq!(result.rounds.len(), 2);
+        assert_eq!(result.rounds[0].eliminated, Some(2));
+        let bob = result.rounds[1].tallies.get(1).unwrap();
+        assert_eq!(bob.candidate, 1);
+        assert_eq!(bob.votes, "3");
+        assert!(result.tie_break.is_none());
+    }
+
+    #[test]
+    fn should_report_elimination_tie_in_instant_runoff() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new()
+                .set_kind(ElectionKind::InstantRunoff)
+                .set_tie_break(TieBreak::CandidateOrder)
+                .set_candidates(vec![
+                    "Alice".to_string(),
+                    "Bob".to_string(),
+                    "Carol".to_string(),
+                ]),
+        );
+        let ballots = [
+            ("u1.testnet", vec![0]),
+            ("u2.testnet", vec![0]),
+            ("u3.testnet", vec![1, 0]),
+            ("u4.testnet", vec![2, 1]),
+        ];
+        for (voter, ranking) in ballots {
+            prepare_env_at(voter, Duration::days(2));
+            contract.vote_ranked(&organization, &election_id.to_string(), ranking);
+        }
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.rounds[0].tied, vec![1, 2]);
+        assert_eq!(result.rounds[0].eliminated, Some(2));
+        assert_eq!(result.rounds[1].tied, vec![0, 1]);
+        assert_eq!(result.rounds[1].eliminated, Some(1));
+        assert_eq!(result.winner, Some(0));
+        let tie_break = result.tie_break.unwrap();
+        assert_eq!(tie_break.policy, TieBreak::CandidateOrder);
+        assert_eq!(tie_break.tied, vec![0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "already finalized")]
+    fn should_finalize_only_once() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(&mut contract, election_id, ElectionInput::new());
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &election_id.to_string());
+        contract.finalize_election(&organization, &election_id.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "not finalized")]
+    fn should_not_return_results_before_finalization() {
+        let contract = create_contract();
+        prepare_env(USER);
+
+        contract.get_results(&account(ORGANIZATION), &"1".to_string());
+    }
+
+    #[test]
+    fn should_tally_encrypted_ballots() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_encryption(3, 2),
+        );
+        for (voter, choice) in [(USER, 1), (OWNER, 1), (ORGANIZATION, 0)] {
+            prepare_env_at(voter, Duration::days(2));
+            contract.vote_encrypted(
+                &organization,
+                &election_id.to_string(),
+                encrypted_ballot(voter, election_id, choice),
+            );
+        }
+
+        let election = contract.get_election(&organization, &election_id.to_string());
+        assert_eq!(election.candidates[1].votes, None);
+        assert_eq!(election.encryption.unwrap().threshold, 2);
+
+        for trustee in [0, 2] {
+            decrypt_tallies(&mut contract, election_id, trustee);
+        }
+        contract.submit_tallies(
+            &organization,
+            &election_id.to_string(),
+            vec![U128(1), U128(2)],
+        );
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        let result = contract.get_results(&organization, &election_id.to_string());
+        assert_eq!(result.winner, Some(1));
+        let election = contract.get_election(&organization, &election_id.to_string());
+        assert_eq!(election.candidates[0].votes, Some("1".to_string()));
+        assert_eq!(election.candidates[1].votes, Some("2".to_string()));
+        let trustees = election.encryption.unwrap().trustees;
+        assert!(trustees[0].decrypted);
+        assert!(!trustees[1].decrypted);
+        assert!(contract
+            .get_decryption_shares(
+                &organization,
+                &election_id.to_string(),
+                &account(&trustee(1))
+            )
+            .is_none());
+        assert_eq!(
+            contract
+                .get_decryption_shares(
+                    &organization,
+                    &election_id.to_string(),
+                    &account(&trustee(2))
+                )
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid ballot proof")]
+    fn should_reject_encrypted_ballot_of_another_voter() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_encryption(3, 2),
+        );
+        prepare_env_at(OWNER, Duration::days(2));
+
+        contract.vote_encrypted(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            encrypted_ballot(USER, election_id, 1),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid decryption of candidate 0")]
+    fn should_reject_invalid_decryption() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_encryption(3, 2),
+        );
+        prepare_env_at(USER, Duration::days(2));
+        contract.vote_encrypted(
+            &organization,
+            &election_id.to_string(),
+            encrypted_ballot(USER, election_id, 1),
+        );
+        prepare_env_at(&trustee(0), Duration::days(4));
+        let (_, secrets) = elgamal::client::trustee_keys(3, 2);
+        let (shares, proofs) = decryption(&contract, election_id, secrets[1]);
+
+        contract.submit_decryption(&organization, &election_id.to_string(), shares, proofs);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough trustees decrypted tallies: 1 of 2")]
+    fn should_not_submit_tallies_before_decryption() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_encryption(3, 2),
+        );
+        decrypt_tallies(&mut contract, election_id, 1);
+
+        contract.submit_tallies(
+            &organization,
+            &election_id.to_string(),
+            vec![U128(0), U128(0)],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid tally of candidate 1")]
+    fn should_reject_wrong_tally() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_encryption(3, 2),
+        );
+        prepare_env_at(USER, Duration::days(2));
+        contract.vote_encrypted(
+            &organization,
+            &election_id.to_string(),
+            encrypted_ballot(USER, election_id, 1),
+        );
+        for trustee in [0, 1] {
+            decrypt_tallies(&mut contract, election_id, trustee);
+        }
+
+        contract.submit_tallies(
+            &organization,
+            &election_id.to_string(),
+            vec![U128(0), U128(2)],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Tallies not submitted yet")]
+    fn should_not_finalize_encrypted_election_before_tallies_submitted() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_encryption(3, 2),
+        );
+        for trustee in [0, 1] {
+            decrypt_tallies(&mut contract, election_id, trustee);
+        }
+
+        contract.finalize_election(&organization, &election_id.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "accepts only encrypted ballots")]
+    fn should_not_accept_plain_vote_in_encrypted_election() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_encryption(1, 1),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "don't match the public key")]
+    fn should_check_public_key_shares_on_create() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+        let mut input = ElectionInput::new().set_encryption(3, 2);
+        input.encryption.as_mut().unwrap().threshold = Some(1);
+
+        contract.create_election(&input);
+    }
+
+    #[test]
+    fn should_vote_anonymously_through_relayer() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        let (proving_key, verifying_key) = membership::client::setup();
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_anonymity(membership::client::voters_root(7), verifying_key),
+        );
+        let (nullifier, proof) =
+            membership::client::prove(&proving_key, 7, 1, anonymous_election_field(election_id));
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_anonymous(
+            &organization,
+            &election_id.to_string(),
+            1,
+            nullifier.to_vec().into(),
+            proof.into(),
+        );
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            contract.have_voted(&organization, &election_id.to_string()),
+            VoteStatus::NotVoted
+        );
+        assert!(contract.ballot_voters.contains(&(
+            organization,
+            election_id,
+            Voter::Nullifier(nullifier)
+        )));
+    }
+
+    #[test]
+    #[should_panic(expected = "already voted")]
+    fn should_not_reuse_nullifier() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        let (proving_key, verifying_key) = membership::client::setup();
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_anonymity(membership::client::voters_root(7), verifying_key),
+        );
+        for (relayer, candidate_id) in [(USER, 1), (OWNER, 0)] {
+            let (nullifier, proof) = membership::client::prove(
+                &proving_key,
+                7,
+                candidate_id,
+                anonymous_election_field(election_id),
+            );
+            prepare_env_at(relayer, Duration::days(2));
+            contract.vote_anonymous(
+                &organization,
+                &election_id.to_string(),
+                candidate_id,
+                nullifier.to_vec().into(),
+                proof.into(),
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid membership proof")]
+    fn should_reject_membership_proof_of_another_election() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        let (proving_key, verifying_key) = membership::client::setup();
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_anonymity(membership::client::voters_root(7), verifying_key),
+        );
+        let (nullifier, proof) =
+            membership::client::prove(&proving_key, 7, 1, anonymous_election_field(2));
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_anonymous(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            nullifier.to_vec().into(),
+            proof.into(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "accepts only anonymous ballots")]
+    fn should_not_accept_plain_vote_in_anonymous_election() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        let (_, verifying_key) = membership::client::setup();
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_anonymity(membership::client::voters_root(7), verifying_key),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    fn should_vote_with_blind_signed_credential() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        contract.register_organization(&organization, None);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_credentials(),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_with_credential(
+            &organization,
+            &election_id.to_string(),
+            1,
+            b"token".to_vec().into(),
+            credential(election_id, b"token"),
+        );
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            contract.have_voted(&organization, &election_id.to_string()),
+            VoteStatus::NotVoted
+        );
+        let view = contract.get_election(&organization, &election_id.to_string());
+        assert_eq!(view.credentials.unwrap().modulus.0, rsa::issuer::modulus());
+    }
+
+    #[test]
+    #[should_panic(expected = "already voted")]
+    fn should_not_reuse_credential() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        contract.register_organization(&organization, None);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_credentials(),
+        );
+        for voter in [USER, OWNER] {
+            prepare_env_at(voter, Duration::days(2));
+            contract.vote_with_credential(
+                &organization,
+                &election_id.to_string(),
+                1,
+                b"token".to_vec().into(),
+                credential(election_id, b"token"),
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid credential signature")]
+    fn should_reject_credential_of_another_election() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        contract.register_organization(&organization, None);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_credentials(),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_with_credential(
+            &organization,
+            &election_id.to_string(),
+            1,
+            b"token".to_vec().into(),
+            credential(2, b"token"),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid issuer key")]
+    fn should_check_issuer_key_on_create() {
+        let mut contract = create_contract();
+        contract.organizations.insert(&account(ORGANIZATION), &0);
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .build());
+        let mut input = ElectionInput::new().set_credentials();
+        input.credentials.as_mut().unwrap().modulus.0.truncate(128);
+
+        contract.create_election(&input);
+    }
+
+    #[test]
+    fn should_manage_allowlist_in_batches() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_allowlist(),
+        );
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(ALLOWLIST_DEPOSIT)
+            .build());
+
+        contract.add_to_allowlist(
+            &election_id.to_string(),
+            vec![
+                account("a.testnet"),
+                account("b.testnet"),
+                account("c.testnet"),
+            ],
+        );
+        contract.add_to_allowlist(&election_id.to_string(), vec![account("a.testnet")]);
+        contract.remove_from_allowlist(
+            &election_id.to_string(),
+            vec![account("a.testnet"), account("d.testnet")],
+        );
+
+        let page = |from: &str, limit| {
+            contract.get_allowlist(
+                &organization,
+                &election_id.to_string(),
+                &from.to_string(),
+                limit,
+            )
+        };
+        assert_eq!(page("0", 1), vec![account("c.testnet")]);
+        assert_eq!(page("1", 10), vec![account("b.testnet")]);
+        assert!(page("2", 10).is_empty());
+        assert!(contract.is_allowed(
+            &organization,
+            &election_id.to_string(),
+            &account("b.testnet")
+        ));
+        assert!(!contract.is_allowed(
+            &organization,
+            &election_id.to_string(),
+            &account("a.testnet")
+        ));
+        let election = contract.get_election(&organization, &election_id.to_string());
+        assert_eq!(election.allowlist_size, Some("2".to_string()));
+    }
 
     #[test]
-    fn should_create_organization() {
+    fn should_refund_allowlist_deposit_excess() {
         let mut contract = create_contract();
-        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_allowlist(),
+        );
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(ALLOWLIST_DEPOSIT)
+            .build());
 
-        contract.register_organization(&organization);
+        contract.add_to_allowlist(&election_id.to_string(), vec![account(USER)]);
 
-        assert!(contract.organizations.contains_key(&organization));
-        assert_eq!(contract.organizations.get(&organization).unwrap(), 0);
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, account(ORGANIZATION));
     }
 
     #[test]
-    #[should_panic(expected = "Only owner")]
-    fn should_not_create_organization_by_non_owner() {
+    #[should_panic(expected = "Attached deposit should cover storage")]
+    fn should_not_add_to_allowlist_without_deposit() {
         let mut contract = create_contract();
-        prepare_env(USER);
-        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_allowlist(),
+        );
+        prepare_env(ORGANIZATION);
 
-        contract.register_organization(&organization);
+        contract.add_to_allowlist(&election_id.to_string(), vec![account(USER)]);
     }
 
     #[test]
-    fn should_create_election() {
+    #[should_panic(expected = "only before election start")]
+    fn should_not_change_allowlist_after_start() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_allowlist(),
+        );
+        prepare_env_at(ORGANIZATION, Duration::days(2));
+
+        contract.add_to_allowlist(&election_id.to_string(), vec![account(USER)]);
+    }
+
+    #[test]
+    fn should_vote_when_on_allowlist() {
         let mut contract = create_contract();
         let organization = account(ORGANIZATION);
-        contract.organizations.insert(&organization, &0);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_allowlist(),
+        );
         testing_env!(context(ORGANIZATION)
-            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+            .attached_deposit(ALLOWLIST_DEPOSIT)
             .build());
-        let input = ElectionInput::new();
+        contract.add_to_allowlist(&election_id.to_string(), vec![account(USER)]);
+        prepare_env_at(USER, Duration::days(2));
 
-        let id = contract.create_election(&input).parse().unwrap();
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
 
-        assert_eq!(id, 0);
-        assert_eq!(contract.organizations.get(&organization).unwrap(), 1);
-        assert!(contract.elections.contains_key(&(organization.clone(), id)));
-        let saved = contract.elections.get(&(organization, id)).unwrap();
-        assert_eq!(saved.start.to_string(), input.start);
-        assert_eq!(saved.end.to_string(), input.end);
-        assert_eq!(saved.title, input.title);
-        assert_eq!(saved.description, input.description);
-        assert_eq!(saved.candidates, input.candidates);
+        assert_eq!(
+            contract.have_voted(&organization, &election_id.to_string()),
+            VoteStatus::Direct
+        );
     }
 
     #[test]
-    #[should_panic(expected = "not registered")]
-    fn should_check_organization_registration_on_create() {
+    #[should_panic(expected = "not allowed to vote")]
+    fn should_not_vote_when_not_on_allowlist() {
         let mut contract = create_contract();
-        contract.organizations.insert(&account(ORGANIZATION), &0);
-        testing_env!(context(USER)
-            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_allowlist(),
+        );
+        testing_env!(context(ORGANIZATION)
+            .attached_deposit(ALLOWLIST_DEPOSIT)
             .build());
+        contract.add_to_allowlist(&election_id.to_string(), vec![account(OWNER)]);
+        prepare_env_at(USER, Duration::days(2));
 
-        contract.create_election(&ElectionInput::new());
+        contract.vote(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            None,
+            None,
+        );
     }
 
     #[test]
-    #[should_panic(expected = "in the future")]
-    fn should_check_election_start_date_on_create() {
+    fn should_vote_with_merkle_proof_and_weight() {
         let mut contract = create_contract();
         let organization = account(ORGANIZATION);
-        contract.organizations.insert(&organization, &0);
-        testing_env!(context(ORGANIZATION)
-            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
-            .build());
-        let input = ElectionInput::new()
-            .set_start(Utc::now().checked_sub_signed(Duration::days(1)).unwrap());
+        let election_id = 1;
+        let leaves = weighted_voters();
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_allowlist_root(merkle::tree::root(&leaves)),
+        );
+        prepare_env_at(USER, Duration::days(2));
 
-        contract.create_election(&input);
+        contract.vote(
+            &organization,
+            &election_id.to_string(),
+            1,
+            Some(merkle_proof(&leaves, 1, 3)),
+            None,
+        );
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            contract.have_voted(&organization, &election_id.to_string()),
+            VoteStatus::Direct
+        );
     }
 
     #[test]
-    #[should_panic(expected = "before end")]
-    fn should_check_election_end_date_on_create() {
+    #[should_panic(expected = "not in the election Merkle allowlist")]
+    fn should_not_vote_with_inflated_merkle_weight() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        let leaves = weighted_voters();
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_allowlist_root(merkle::tree::root(&leaves)),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            Some(merkle_proof(&leaves, 1, 30)),
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Merkle proof of eligibility expected")]
+    fn should_require_merkle_proof() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_allowlist_root(merkle::tree::root(&weighted_voters())),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    fn should_query_token_balance_on_vote() {
         let mut contract = create_contract();
         let organization = account(ORGANIZATION);
-        contract.organizations.insert(&organization, &0);
-        testing_env!(context(ORGANIZATION)
-            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
-            .build());
-        let input = ElectionInput::new().set_end(Utc::now());
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_token_gate(10, TokenWeighting::Balance),
+        );
+        prepare_env_at(USER, Duration::days(2));
 
-        contract.create_election(&input);
+        contract.vote_with_tokens(&organization, &election_id.to_string(), 1);
+
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[0].receiver_id, account(TOKEN));
+        assert_eq!(
+            contract.have_voted(&organization, &election_id.to_string()),
+            VoteStatus::NotVoted
+        );
     }
 
     #[test]
-    #[should_panic(expected = "provided")]
-    fn should_check_that_candidates_are_provided() {
+    fn should_weight_ballot_by_token_balance() {
         let mut contract = create_contract();
         let organization = account(ORGANIZATION);
-        contract.organizations.insert(&organization, &0);
-        testing_env!(context(ORGANIZATION)
-            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
-            .build());
-        let input = ElectionInput::new().set_candidates(vec![]);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_token_gate(10, TokenWeighting::SquareRoot),
+        );
+        prepare_env_at(USER, Duration::days(2));
 
-        contract.create_election(&input);
+        contract.on_token_balance(
+            &organization,
+            &election_id.to_string(),
+            1,
+            account(USER),
+            Ok(U128(400)),
+        );
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            20
+        );
+        assert_eq!(
+            contract.have_voted(&organization, &election_id.to_string()),
+            VoteStatus::Direct
+        );
     }
 
     #[test]
-    #[should_panic(expected = "provided")]
-    fn should_check_that_more_than_one_candidate_provided() {
+    #[should_panic(expected = "Token balance is below the election minimum")]
+    fn should_not_count_ballot_below_min_balance() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_token_gate(10, TokenWeighting::Balance),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.on_token_balance(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            account(USER),
+            Ok(U128(9)),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Election already ended")]
+    fn should_not_count_token_ballot_after_election_end() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_token_gate(10, TokenWeighting::Balance),
+        );
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.on_token_balance(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            account(USER),
+            Ok(U128(10)),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Election accepts only ballots weighted by tokens")]
+    fn should_not_vote_without_tokens_in_token_gated_election() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_token_gate(10, TokenWeighting::Balance),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    fn should_query_nft_owner_on_vote() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_nft_contract(),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote_with_nft(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            "7".to_string(),
+        );
+
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[0].receiver_id, account(NFT));
+    }
+
+    #[test]
+    fn should_vote_once_per_nft() {
         let mut contract = create_contract();
         let organization = account(ORGANIZATION);
-        contract.organizations.insert(&organization, &0);
-        testing_env!(context(ORGANIZATION)
-            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
-            .build());
-        let input = ElectionInput::new().set_candidates(vec!["Alice".to_string()]);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_nft_contract(),
+        );
+        prepare_env_at(USER, Duration::days(2));
 
-        contract.create_election(&input);
+        for token_id in ["7", "8"] {
+            contract.on_nft_token(
+                &organization,
+                &election_id.to_string(),
+                1,
+                token_id.to_string(),
+                account(USER),
+                Ok(Some(nft(token_id, USER))),
+            );
+        }
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            2
+        );
+        assert!(contract.have_token_voted(
+            &organization,
+            &election_id.to_string(),
+            "7".to_string()
+        ));
+        assert!(!contract.have_token_voted(
+            &organization,
+            &election_id.to_string(),
+            "9".to_string()
+        ));
     }
 
     #[test]
-    #[should_panic(expected = "paid")]
-    fn should_require_deposit_on_create() {
+    #[should_panic(expected = "Token already voted")]
+    fn should_not_vote_twice_with_nft() {
         let mut contract = create_contract();
-        contract.organizations.insert(&account(ORGANIZATION), &0);
-        prepare_env(ORGANIZATION);
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_nft_contract(),
+        );
+        prepare_env_at(USER, Duration::days(2));
+        contract.on_nft_token(
+            &organization,
+            &election_id.to_string(),
+            1,
+            "7".to_string(),
+            account(USER),
+            Ok(Some(nft("7", USER))),
+        );
 
-        contract.create_election(&ElectionInput::new());
+        contract.vote_with_nft(&organization, &election_id.to_string(), 0, "7".to_string());
     }
 
     #[test]
-    #[should_panic(expected = "paid")]
-    fn should_require_exact_deposit_on_create() {
+    #[should_panic(expected = "Account doesn't own the token")]
+    fn should_not_vote_with_nft_of_other_account() {
         let mut contract = create_contract();
-        contract.organizations.insert(&account(ORGANIZATION), &0);
-        testing_env!(context(ORGANIZATION)
-            .attached_deposit(EXPECTED_CREATE_ELECTION_COST * 2)
-            .build());
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_nft_contract(),
+        );
+        prepare_env_at(USER, Duration::days(2));
 
-        contract.create_election(&ElectionInput::new());
+        contract.on_nft_token(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            "7".to_string(),
+            account(USER),
+            Ok(Some(nft("7", OWNER))),
+        );
     }
 
     #[test]
-    #[should_panic(expected = "256")]
-    fn should_allow_maximum_256_candidates() {
+    #[should_panic(expected = "Election already ended")]
+    fn should_not_count_nft_ballot_after_election_end() {
         let mut contract = create_contract();
-        contract.organizations.insert(&account(ORGANIZATION), &0);
-        testing_env!(context(ORGANIZATION)
-            .attached_deposit(EXPECTED_CREATE_ELECTION_COST)
-            .build());
+        let election_id = 1;
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_nft_contract(),
+        );
+        prepare_env_at(USER, Duration::days(4));
 
-        contract.create_election(
-            &ElectionInput::new()
-                .set_candidates((0..).take(257).map(|_| "Bob".to_string()).collect()),
+        contract.on_nft_token(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            "7".to_string(),
+            account(USER),
+            Ok(Some(nft("7", USER))),
+        );
+    }
+
+    fn nft(token_id: &str, owner: &str) -> NftToken {
+        NftToken {
+            token_id: token_id.to_string(),
+            owner_id: account(owner),
+        }
+    }
+
+    #[test]
+    fn should_vote_with_voucher() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        let election_id = 1;
+        register_with_voucher_key(&mut contract);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_vouchers(),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote(
+            &organization,
+            &election_id.to_string(),
+            1,
+            None,
+            Some(voucher(election_id, USER, Duration::days(3))),
+        );
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            1
         );
     }
 
     #[test]
-    fn should_fetch_election_count() {
-        let count = 14;
-        let organization = account(ORGANIZATION);
+    #[should_panic(expected = "Voucher expired")]
+    fn should_not_vote_with_expired_voucher() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        register_with_voucher_key(&mut contract);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_vouchers(),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            None,
+            Some(voucher(election_id, USER, Duration::days(1))),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid voucher signature")]
+    fn should_not_vote_with_voucher_of_other_account() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        register_with_voucher_key(&mut contract);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_vouchers(),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            None,
+            Some(voucher(election_id, OWNER, Duration::days(3))),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Voucher expected")]
+    fn should_require_voucher() {
+        let mut contract = create_contract();
+        let election_id = 1;
+        register_with_voucher_key(&mut contract);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_vouchers(),
+        );
+        prepare_env_at(USER, Duration::days(2));
+
+        contract.vote(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            1,
+            None,
+            None,
+        );
+    }
+
+    const VOUCHER_SECRET: [u8; 32] = [7; 32];
+
+    fn register_with_voucher_key(contract: &mut Elections) {
+        contract.register_organization(
+            &account(ORGANIZATION),
+            Some(voucher::signer::public_key(&VOUCHER_SECRET).into()),
+        );
+    }
+
+    /// Voucher of test organization, valid until `expiry` from now.
+    fn voucher(election_id: ElectionId, voter: &str, expiry: Duration) -> Voucher {
+        let expiry = nanoseconds(Utc::now().checked_add_signed(expiry).unwrap());
+        let message = (account(ORGANIZATION), election_id, account(voter), expiry)
+            .try_to_vec()
+            .unwrap();
+        Voucher {
+            expiry: expiry.to_string(),
+            signature: voucher::signer::sign(&VOUCHER_SECRET, &message).into(),
+        }
+    }
+
+    #[test]
+    fn should_count_ballots_through_delegation_chains() {
         let mut contract = create_contract();
-        contract.organizations.insert(&organization, &count);
+        let organization = account(ORGANIZATION);
+        let election_id = 0;
+        contract.register_organization(&organization, None);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_delegation(),
+        );
         prepare_env(USER);
+        contract.delegate(&organization, None, account(OWNER));
+        prepare_env("carol.testnet");
+        contract.delegate(&organization, Some(election_id.to_string()), account(USER));
+        prepare_env_at(OWNER, Duration::days(2));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+        prepare_env_at(USER, Duration::days(4));
 
-        let result = contract.elections_count(&organization);
+        assert!(contract.count_delegated_ballots(&organization, &election_id.to_string(), 10));
 
-        assert_eq!(result, count.to_string());
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            contract
+                .ballots_count
+                .get(&(organization.clone(), election_id))
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            contract.have_voted(&organization, &election_id.to_string()),
+            VoteStatus::Delegated
+        );
     }
 
     #[test]
-    fn should_return_true_if_voted() {
+    fn should_override_delegation_with_direct_ballot() {
         let mut contract = create_contract();
         let organization = account(ORGANIZATION);
-        let user = account(USER);
-        let election_id = 12;
-        contract
-            .voters
-            .insert(&(organization.clone(), election_id, user));
+        let election_id = 0;
+        contract.register_organization(&organization, None);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_delegation(),
+        );
         prepare_env(USER);
+        contract.delegate(&organization, None, account(OWNER));
+        prepare_env_at(OWNER, Duration::days(2));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+        prepare_env_at(USER, Duration::days(2));
+        contract.vote(&organization, &election_id.to_string(), 0, None, None);
+        prepare_env_at(USER, Duration::days(4));
 
-        let result = contract.have_voted(&organization, &election_id.to_string());
+        assert!(contract.count_delegated_ballots(&organization, &election_id.to_string(), 10));
 
-        assert!(result);
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 0))
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            contract.have_voted(&organization, &election_id.to_string()),
+            VoteStatus::Direct
+        );
     }
 
     #[test]
-    fn should_return_false_if_not_voted() {
+    fn should_skip_delegation_cycles() {
         let mut contract = create_contract();
         let organization = account(ORGANIZATION);
-        let election_id = 12;
+        let election_id = 0;
+        contract.register_organization(&organization, None);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_delegation(),
+        );
         prepare_env(USER);
+        contract.delegate(&organization, None, account(OWNER));
+        prepare_env(OWNER);
+        contract.delegate(&organization, None, account(USER));
+        prepare_env_at("carol.testnet", Duration::days(2));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+        prepare_env_at(USER, Duration::days(4));
 
-        let result = contract.have_voted(&organization, &election_id.to_string());
+        assert!(contract.count_delegated_ballots(&organization, &election_id.to_string(), 10));
 
-        assert!(!result);
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            contract.have_voted(&organization, &election_id.to_string()),
+            VoteStatus::NotVoted
+        );
     }
 
     #[test]
-    fn should_fetch_election() {
-        let organization = account(ORGANIZATION);
+    fn should_not_count_delegation_chains_exceeding_max_depth() {
         let mut contract = create_contract();
-        let election_id = 1;
-        let input = ElectionInput::new();
-        contract
-            .elections
-            .insert(&(organization.clone(), election_id), &Election::new(&input));
-        let bob_votes = 3;
-        contract
-            .votes
-            .insert(&(organization.clone(), election_id, 1), &bob_votes);
-        prepare_env(USER);
+        let organization = account(ORGANIZATION);
+        let election_id = 0;
+        contract.register_organization(&organization, None);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_delegation(),
+        );
+        let delegates: Vec<String> = (0..=MAX_DELEGATION_DEPTH + 1)
+            .map(|i| format!("d{}.testnet", i))
+            .collect();
+        for pair in delegates.windows(2) {
+            prepare_env(&pair[0]);
+            contract.delegate(&organization, None, account(&pair[1]));
+        }
+        prepare_env_at(delegates.last().unwrap(), Duration::days(2));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+        prepare_env_at(&delegates[0], Duration::days(4));
 
-        let result = contract.get_election(&organization, &election_id.to_string());
+        assert!(contract.count_delegated_ballots(&organization, &election_id.to_string(), 100));
 
-        assert_eq!(result.start, input.start);
-        assert_eq!(result.end, input.end);
-        assert_eq!(result.title, input.title);
-        assert_eq!(result.description, input.description);
-        assert_eq!(result.candidates.len(), 2);
-        let alice = result.candidates.get(0).unwrap();
-        assert_eq!(alice.name, "Alice".to_string());
-        assert_eq!(alice.votes, "0");
-        let bob = result.candidates.get(1).unwrap();
-        assert_eq!(bob.name, "Bob".to_string());
-        assert_eq!(bob.votes, bob_votes.to_string());
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            MAX_DELEGATION_DEPTH as u128 + 1
+        );
+        assert_eq!(
+            contract.have_voted(&organization, &election_id.to_string()),
+            VoteStatus::NotVoted
+        );
     }
 
     #[test]
-    #[should_panic(expected = "started")]
-    fn should_panic_if_election_not_started() {
+    fn should_ignore_organization_delegation_made_after_election_end() {
         let mut contract = create_contract();
         let organization = account(ORGANIZATION);
-        let election_id = 1;
-        contract.elections.insert(
-            &(organization.clone(), election_id),
-            &Election::new(&ElectionInput::new()),
+        let election_id = 0;
+        contract.register_organization(&organization, None);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_delegation(),
         );
-        prepare_env(USER);
+        prepare_env_at(OWNER, Duration::days(2));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+        prepare_env_at(USER, Duration::days(4));
+        contract.delegate(&organization, None, account(OWNER));
+
+        assert!(contract.count_delegated_ballots(&organization, &election_id.to_string(), 10));
 
-        contract.vote(&organization, &election_id.to_string(), 0);
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            1
+        );
     }
 
     #[test]
-    #[should_panic(expected = "ended")]
-    fn should_panic_if_election_finished() {
+    fn should_not_change_tally_by_delegation_changes_after_election_end() {
         let mut contract = create_contract();
         let organization = account(ORGANIZATION);
-        let election_id = 1;
-        contract.elections.insert(
-            &(organization.clone(), election_id),
-            &Election::new(&ElectionInput::new()),
+        let election_id = 0;
+        contract.register_organization(&organization, None);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_delegation(),
         );
-        testing_env!(context(USER)
-            .block_timestamp(nanoseconds(
-                Utc::now().checked_add_signed(Duration::days(4)).unwrap()
-            ))
-            .build());
+        prepare_env(USER);
+        contract.delegate(&organization, None, account(OWNER));
+        prepare_env("carol.testnet");
+        contract.delegate(&organization, None, account(USER));
+        prepare_env_at(OWNER, Duration::days(2));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+        prepare_env_at(USER, Duration::days(4));
+        contract.undelegate(&organization, None);
+        prepare_env_at("carol.testnet", Duration::days(4));
+        contract.delegate(&organization, None, account(ORGANIZATION));
+
+        assert!(contract.count_delegated_ballots(&organization, &election_id.to_string(), 10));
 
-        contract.vote(&organization, &election_id.to_string(), 0);
+        contract.finalize_election(&organization, &election_id.to_string());
+
+        assert_eq!(
+            contract
+                .votes
+                .get(&(organization.clone(), election_id, 1))
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            contract.get_delegate(&organization, None, &account("carol.testnet")),
+            Some(account(ORGANIZATION))
+        );
+        assert_eq!(
+            contract.get_delegate(&organization, None, &account(USER)),
+            None
+        );
     }
 
     #[test]
-    fn should_vote() {
+    fn should_count_delegated_ballots_in_batches() {
         let mut contract = create_contract();
         let organization = account(ORGANIZATION);
-        let election_id = 1;
-        contract.elections.insert(
-            &(organization.clone(), election_id),
-            &Election::new(&ElectionInput::new()),
+        let election_id = 0;
+        contract.register_organization(&organization, None);
+        insert_election(
+            &mut contract,
+            election_id,
+            ElectionInput::new().set_delegation(),
         );
-        testing_env!(context(USER)
-            .block_timestamp(nanoseconds(
-                Utc::now().checked_add_signed(Duration::days(2)).unwrap()
-            ))
-            .build());
-        let candidate_id = 1;
+        for delegator in [USER, "carol.testnet", "dave.testnet"] {
+            prepare_env(delegator);
+            contract.delegate(&organization, None, account(OWNER));
+        }
+        prepare_env_at(OWNER, Duration::days(2));
+        contract.vote(&organization, &election_id.to_string(), 1, None, None);
+        prepare_env_at(USER, Duration::days(4));
 
-        contract.vote(&organization, &election_id.to_string(), candidate_id);
+        assert!(!contract.count_delegated_ballots(&organization, &election_id.to_string(), 2));
+        assert!(contract.count_delegated_ballots(&organization, &election_id.to_string(), 2));
+        contract.finalize_election(&organization, &election_id.to_string());
 
         assert_eq!(
             contract
                 .votes
-                .get(&(organization.clone(), election_id, candidate_id))
+                .get(&(organization.clone(), election_id, 1))
                 .unwrap(),
-            1
+            4
         );
-        assert!(contract
-            .voters
-            .contains(&(organization.clone(), election_id, account(USER))));
     }
 
     #[test]
-    #[should_panic(expected = "already voted")]
-    fn should_prohibit_to_vote_twice() {
+    #[should_panic(expected = "Delegated ballots not counted yet")]
+    fn should_not_finalize_before_counting_delegated_ballots() {
         let mut contract = create_contract();
         let organization = account(ORGANIZATION);
-        let election_id = 1;
-        contract.elections.insert(
-            &(organization.clone(), election_id),
-            &Election::new(&ElectionInput::new()),
+        contract.register_organization(&organization, None);
+        insert_election(&mut contract, 0, ElectionInput::new().set_delegation());
+        prepare_env(USER);
+        contract.delegate(&organization, None, account(OWNER));
+        prepare_env_at(USER, Duration::days(4));
+
+        contract.finalize_election(&organization, &"0".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Election doesn't support delegation")]
+    fn should_not_delegate_in_election_without_delegation() {
+        let mut contract = create_contract();
+        let organization = account(ORGANIZATION);
+        contract.register_organization(&organization, None);
+        insert_election(&mut contract, 0, ElectionInput::new());
+        prepare_env(USER);
+
+        contract.delegate(&organization, Some("0".to_string()), account(OWNER));
+    }
+
+    /// Merkle leaves of `OWNER` with weight 1, `USER` with weight 3 & one more voter.
+    fn weighted_voters() -> Vec<Vec<u8>> {
+        vec![
+            merkle::leaf(&account(OWNER), 1),
+            merkle::leaf(&account(USER), 3),
+            merkle::leaf(&account("carol.testnet"), 2),
+        ]
+    }
+
+    fn merkle_proof(leaves: &[Vec<u8>], index: usize, weight: u128) -> MerkleProof {
+        MerkleProof {
+            siblings: merkle::tree::proof(leaves, index)
+                .into_iter()
+                .map(Base64VecU8::from)
+                .collect(),
+            weight: Some(weight.to_string()),
+        }
+    }
+
+    fn issuer_key() -> IssuerKeyInput {
+        IssuerKeyInput {
+            modulus: rsa::issuer::modulus().into(),
+            exponent: rsa::issuer::exponent().into(),
+        }
+    }
+
+    /// Token signature of test issuer, unblinded by voter.
+    fn credential(election_id: ElectionId, token: &[u8]) -> Base64VecU8 {
+        let message = (account(ORGANIZATION), election_id, token.to_vec())
+            .try_to_vec()
+            .unwrap();
+        rsa::issuer::blind_sign(&message, 987_654_321).into()
+    }
+
+    fn anonymous_election_field(election_id: ElectionId) -> ark_bn254::Fr {
+        membership::election_field(&(account(ORGANIZATION), election_id).try_to_vec().unwrap())
+    }
+
+    fn trustee(i: usize) -> String {
+        format!("trustee{}.testnet", i + 1)
+    }
+
+    fn encrypted_ballot(voter: &str, election_id: ElectionId, choice: usize) -> EncryptedBallot {
+        let (public_key, _) = elgamal::client::trustee_keys(3, 2);
+        let context = (account(ORGANIZATION), election_id, account(voter))
+            .try_to_vec()
+            .unwrap();
+        let (ciphertexts, proofs, sum_proof) =
+            elgamal::client::encrypt_ballot(&context, public_key, 2, choice);
+        EncryptedBallot {
+            ciphertexts: ciphertexts
+                .iter()
+                .map(|ciphertext| ciphertext.to_bytes().to_vec().into())
+                .collect(),
+            proofs: proofs.into_iter().map(Base64VecU8::from).collect(),
+            sum_proof: sum_proof.into(),
+        }
+    }
+
+    /// Partial decryption of election tallies with a secret key share.
+    fn decryption(
+        contract: &Elections,
+        election_id: ElectionId,
+        secret: Scalar,
+    ) -> (Vec<Base64VecU8>, Vec<Base64VecU8>) {
+        contract
+            .get_encrypted_tallies(&account(ORGANIZATION), &election_id.to_string())
+            .iter()
+            .enumerate()
+            .map(|(i, tally)| {
+                let context = (account(ORGANIZATION), election_id, i as CandidateId)
+                    .try_to_vec()
+                    .unwrap();
+                let tally = elgamal::Ciphertext::from_bytes(&tally.0).unwrap();
+                let (share, proof) = elgamal::client::decrypt_share(&context, secret, &tally);
+                (share.compress().to_bytes().to_vec().into(), proof.into())
+            })
+            .unzip()
+    }
+
+    fn decrypt_tallies(contract: &mut Elections, election_id: ElectionId, trustee_index: usize) {
+        let (_, secrets) = elgamal::client::trustee_keys(3, 2);
+        prepare_env_at(&trustee(trustee_index), Duration::days(4));
+        let (shares, proofs) = decryption(contract, election_id, secrets[trustee_index]);
+        contract.submit_decryption(
+            &account(ORGANIZATION),
+            &election_id.to_string(),
+            shares,
+            proofs,
         );
-        testing_env!(context(USER)
-            .block_timestamp(nanoseconds(
-                Utc::now().checked_add_signed(Duration::days(2)).unwrap()
-            ))
-            .build());
-        let candidate_id = 1;
+    }
+
+    fn secret_election() -> ElectionInput {
+        ElectionInput::new()
+            .set_reveal_end(Utc::now().checked_add_signed(Duration::days(5)).unwrap())
+    }
+
+    fn commitment(candidate_id: u8, salt: &[u8]) -> Base64VecU8 {
+        let mut preimage = vec![candidate_id];
+        preimage.extend(salt);
+        env::sha256(&preimage).into()
+    }
 
-        contract.vote(&organization, &election_id.to_string(), candidate_id);
-        contract.vote(&organization, &election_id.to_string(), candidate_id);
+    fn grades_kind() -> ElectionKind {
+        ElectionKind::MajorityJudgment {
+            grades: vec!["Poor".to_string(), "Fair".to_string(), "Good".to_string()],
+        }
     }
 
     fn create_contract() -> Elections {
@@ -628,6 +8741,19 @@ mod tests {
         testing_env!(context(predecessor).build())
     }
 
+    fn prepare_env_at(predecessor: &str, offset: Duration) {
+        testing_env!(context(predecessor)
+            .block_timestamp(nanoseconds(Utc::now().checked_add_signed(offset).unwrap()))
+            .build())
+    }
+
+    fn insert_election(contract: &mut Elections, election_id: ElectionId, input: ElectionInput) {
+        contract.elections.insert(
+            &(account(ORGANIZATION), election_id),
+            &Election::new(&input),
+        );
+    }
+
     fn context(predecessor: &str) -> VMContextBuilder {
         let mut builder = VMContextBuilder::new();
         builder
@@ -654,9 +8780,150 @@ mod tests {
                 title: "My Election".to_string(),
                 description: "My Description".to_string(),
                 candidates: vec!["Alice".to_string(), "Bob".to_string()],
+                kind: ElectionKind::Plurality,
+                seats: 1,
+                runoff: None,
+                reveal_end: None,
+                results_visibility: ResultsVisibility::Live,
+                show_turnout: false,
+                encryption: None,
+                anonymity: None,
+                credentials: None,
+                allowlist: false,
+                allowlist_root: None,
+                token_gate: None,
+                nft_contract: None,
+                vouchers: false,
+                delegation: false,
+                blank_options: vec![],
+                quorum: None,
+                majority: Majority::Plurality,
+                tie_break: TieBreak::None,
             }
         }
 
+        fn set_quorum(mut self, quorum: QuorumInput) -> Self {
+            self.quorum = Some(quorum);
+            self
+        }
+
+        fn set_majority(mut self, majority: Majority) -> Self {
+            self.majority = majority;
+            self
+        }
+
+        fn set_tie_break(mut self, tie_break: TieBreak) -> Self {
+            self.tie_break = tie_break;
+            self
+        }
+
+        fn set_blank_options(mut self, options: Vec<BlankOption>) -> Self {
+            self.blank_options = options;
+            self
+        }
+
+        fn set_delegation(mut self) -> Self {
+            self.delegation = true;
+            self
+        }
+
+        fn set_vouchers(mut self) -> Self {
+            self.vouchers = true;
+            self
+        }
+
+        fn set_nft_contract(mut self) -> Self {
+            self.nft_contract = Some(account(NFT));
+            self
+        }
+
+        fn set_token_gate(mut self, min_balance: u128, weighting: TokenWeighting) -> Self {
+            self.token_gate = Some(TokenGateInput {
+                token_id: account(TOKEN),
+                min_balance: Some(min_balance.to_string()),
+                weighting,
+            });
+            self
+        }
+
+        fn set_allowlist_root(mut self, root: Vec<u8>) -> Self {
+            self.allowlist_root = Some(root.into());
+            self
+        }
+
+        fn set_allowlist(mut self) -> Self {
+            self.allowlist = true;
+            self
+        }
+
+        fn set_credentials(mut self) -> Self {
+            self.credentials = Some(issuer_key());
+            self
+        }
+
+        fn set_anonymity(mut self, voters_root: [u8; 32], verifying_key: Vec<u8>) -> Self {
+            self.anonymity = Some(AnonymityInput {
+                voters_root: voters_root.to_vec().into(),
+                verifying_key: verifying_key.into(),
+            });
+            self
+        }
+
+        /// Encrypted ballots with trustee keys from [elgamal::client::trustee_keys].
+        fn set_encryption(mut self, trustees: u64, threshold: u8) -> Self {
+            let (public_key, secrets) = elgamal::client::trustee_keys(trustees, threshold.into());
+            self.encryption = Some(EncryptionInput {
+                public_key: public_key.compress().to_bytes().to_vec().into(),
+                trustees: secrets
+                    .iter()
+                    .enumerate()
+                    .map(|(i, secret)| TrusteeInput {
+                        account_id: account(&trustee(i)),
+                        public_share: elgamal::client::public_share(secret)
+                            .compress()
+                            .to_bytes()
+                            .to_vec()
+                            .into(),
+                    })
+                    .collect(),
+                threshold: Some(threshold),
+            });
+            self
+        }
+
+        fn set_results_visibility(
+            mut self,
+            visibility: ResultsVisibility,
+            show_turnout: bool,
+        ) -> Self {
+            self.results_visibility = visibility;
+            self.show_turnout = show_turnout;
+            self
+        }
+
+        fn set_reveal_end(mut self, reveal_end: DateTime<Utc>) -> Self {
+            self.reveal_end = Some(nanoseconds(reveal_end).to_string());
+            self
+        }
+
+        fn set_runoff(mut self, threshold: Option<u8>, duration: Duration) -> Self {
+            self.runoff = Some(RunoffInput {
+                threshold,
+                duration: duration.num_nanoseconds().unwrap().to_string(),
+            });
+            self
+        }
+
+        fn set_seats(mut self, seats: u8) -> Self {
+            self.seats = seats;
+            self
+        }
+
+        fn set_kind(mut self, kind: ElectionKind) -> Self {
+            self.kind = kind;
+            self
+        }
+
         fn set_start(mut self, start: DateTime<Utc>) -> Self {
             self.start = nanoseconds(start).to_string();
             self
@@ -0,0 +1,222 @@
+use std::convert::TryInto;
+
+use ark_bn254::{Bn254, Fr};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::{prepare_verifying_key, Groth16, Proof, VerifyingKey};
+use ark_serialize::CanonicalDeserialize;
+use near_sdk::env;
+
+/// Public inputs of a membership circuit, in order:
+/// voters Merkle root, nullifier, candidate id and election field.
+const PUBLIC_INPUTS: usize = 4;
+
+/// Parses canonical 32 bytes little-endian BN254 scalar field element.
+pub fn decode_field(bytes: &[u8]) -> Option<Fr> {
+    if bytes.len() != 32 {
+        return None;
+    }
+    Fr::deserialize_compressed(bytes).ok()
+}
+
+/// Parses compressed Groth16 verifying key of a circuit with membership public inputs.
+pub fn decode_verifying_key(bytes: &[u8]) -> Option<VerifyingKey<Bn254>> {
+    VerifyingKey::deserialize_compressed(bytes)
+        .ok()
+        .filter(|key: &VerifyingKey<Bn254>| key.gamma_abc_g1.len() == PUBLIC_INPUTS + 1)
+}
+
+/// Election field element, `sha256(context)` reduced modulo field order.
+///
+/// Circuit should derive nullifier from voter's secret and this value,
+/// so that nullifiers are unlinkable between elections.
+pub fn election_field(context: &[u8]) -> Fr {
+    Fr::from_le_bytes_mod_order(&env::sha256(context))
+}
+
+/// Checks compressed Groth16 proof of voter membership.
+///
+/// Proof is valid only for the given voters root, nullifier and ballot in the given election,
+/// so relayer submitting it can't change any of them.
+pub fn verify(
+    verifying_key: &VerifyingKey<Bn254>,
+    proof: &[u8],
+    voters_root: Fr,
+    nullifier: Fr,
+    candidate_id: u8,
+    election: Fr,
+) -> bool {
+    let proof: Proof<Bn254> = match Proof::deserialize_compressed(proof) {
+        Ok(proof) => proof,
+        Err(_) => return false,
+    };
+    let inputs: [Fr; PUBLIC_INPUTS] = [voters_root, nullifier, candidate_id.into(), election];
+    Groth16::<Bn254>::verify_proof(&prepare_verifying_key(verifying_key), &proof, &inputs)
+        .unwrap_or(false)
+}
+
+/// Canonical bytes of a field element.
+pub fn encode_field(value: &Fr) -> [u8; 32] {
+    let bytes = value.into_bigint().to_bytes_le();
+    bytes.as_slice().try_into().unwrap()
+}
+
+/// Client side of the protocol. Used to prepare test data.
+#[cfg(test)]
+pub mod client {
+    use ark_ff::Field;
+    use ark_groth16::ProvingKey;
+    use ark_relations::lc;
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+    use ark_serialize::CanonicalSerialize;
+
+    use super::*;
+
+    /// Toy membership circuit: single sibling "Merkle path" with non-cryptographic hashing.
+    ///
+    /// Has the same public inputs as real circuits, which is enough to exercise verification.
+    /// Identity commitment is `secret²`, root is `commitment² + sibling`
+    /// and nullifier is `secret · election`.
+    #[derive(Clone, Default)]
+    struct ToyMembership {
+        secret: Fr,
+        sibling: Fr,
+        candidate: Fr,
+        election: Fr,
+    }
+
+    impl ToyMembership {
+        fn root(&self) -> Fr {
+            self.secret.square().square() + self.sibling
+        }
+
+        fn nullifier(&self) -> Fr {
+            self.secret * self.election
+        }
+    }
+
+    impl ConstraintSynthesizer<Fr> for ToyMembership {
+        fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+            let root = cs.new_input_variable(|| Ok(self.root()))?;
+            let nullifier = cs.new_input_variable(|| Ok(self.nullifier()))?;
+            let candidate = cs.new_input_variable(|| Ok(self.candidate))?;
+            let election = cs.new_input_variable(|| Ok(self.election))?;
+            let secret = cs.new_witness_variable(|| Ok(self.secret))?;
+            let sibling = cs.new_witness_variable(|| Ok(self.sibling))?;
+            let commitment = cs.new_witness_variable(|| Ok(self.secret.square()))?;
+            let candidate_square = cs.new_witness_variable(|| Ok(self.candidate.square()))?;
+
+            cs.enforce_constraint(lc!() + secret, lc!() + secret, lc!() + commitment)?;
+            cs.enforce_constraint(
+                lc!() + commitment,
+                lc!() + commitment,
+                lc!() + root - sibling,
+            )?;
+            cs.enforce_constraint(lc!() + secret, lc!() + election, lc!() + nullifier)?;
+            // Binds the ballot to the proof.
+            cs.enforce_constraint(
+                lc!() + candidate,
+                lc!() + candidate,
+                lc!() + candidate_square,
+            )
+        }
+    }
+
+    /// Generates proving key and compressed verifying key of the toy circuit.
+    pub fn setup() -> (ProvingKey<Bn254>, Vec<u8>) {
+        let proving_key = Groth16::<Bn254>::generate_random_parameters_with_reduction(
+            ToyMembership::default(),
+            &mut ark_std::test_rng(),
+        )
+        .unwrap();
+        let mut verifying_key = vec![];
+        proving_key
+            .vk
+            .serialize_compressed(&mut verifying_key)
+            .unwrap();
+        (proving_key, verifying_key)
+    }
+
+    /// Voters root of a single voter with the given secret.
+    pub fn voters_root(secret: u64) -> [u8; 32] {
+        encode_field(
+            &ToyMembership {
+                secret: secret.into(),
+                sibling: 1u64.into(),
+                ..Default::default()
+            }
+            .root(),
+        )
+    }
+
+    /// Proves membership of a voter with the given secret. Returns nullifier and proof.
+    pub fn prove(
+        proving_key: &ProvingKey<Bn254>,
+        secret: u64,
+        candidate_id: u8,
+        election: Fr,
+    ) -> ([u8; 32], Vec<u8>) {
+        let circuit = ToyMembership {
+            secret: secret.into(),
+            sibling: 1u64.into(),
+            candidate: candidate_id.into(),
+            election,
+        };
+        let nullifier = encode_field(&circuit.nullifier());
+        let proof = Groth16::<Bn254>::create_random_proof_with_reduction(
+            circuit,
+            proving_key,
+            &mut ark_std::test_rng(),
+        )
+        .unwrap();
+        let mut bytes = vec![];
+        proof.serialize_compressed(&mut bytes).unwrap();
+        (nullifier, bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::client::*;
+    use super::*;
+
+    #[test]
+    fn should_verify_membership_proof() {
+        let (proving_key, verifying_key) = setup();
+        let verifying_key = decode_verifying_key(&verifying_key).unwrap();
+        let root = decode_field(&voters_root(7)).unwrap();
+        let election = election_field(b"election");
+
+        let (nullifier, proof) = prove(&proving_key, 7, 1, election);
+        let nullifier = decode_field(&nullifier).unwrap();
+
+        assert!(verify(&verifying_key, &proof, root, nullifier, 1, election));
+        assert!(!verify(
+            &verifying_key,
+            &proof,
+            root,
+            nullifier,
+            0,
+            election
+        ));
+        assert!(!verify(
+            &verifying_key,
+            &proof,
+            root,
+            nullifier,
+            1,
+            election_field(b"other election")
+        ));
+    }
+
+    #[test]
+    fn should_reject_non_canonical_field_elements() {
+        let modulus = Fr::MODULUS.to_bytes_le();
+
+        assert_eq!(decode_field(&modulus), None);
+        assert_eq!(decode_field(&[0; 31]), None);
+        assert_eq!(
+            decode_field(&[1; 32]).map(|f| encode_field(&f)),
+            Some([1; 32])
+        );
+    }
+}
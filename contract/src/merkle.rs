@@ -0,0 +1,98 @@
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::env;
+
+use crate::VoterId;
+
+/// Leaf of a voter: `sha256(0x00 || borsh(account_id, weight))`.
+pub fn leaf(account_id: &VoterId, weight: u128) -> Vec<u8> {
+    let mut data = vec![0];
+    data.extend((account_id, weight).try_to_vec().unwrap());
+    env::sha256(&data)
+}
+
+/// Parent node: `sha256(0x01 || min(a, b) || max(a, b))`.
+///
+/// Children are sorted, so proofs don't need to specify sides of the siblings.
+fn node(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    env::sha256(&[&[1], left, right].concat())
+}
+
+/// Checks that leaf belongs to the tree with given root. `siblings` go from the leaf up.
+pub fn verify(root: &[u8], leaf: &[u8], siblings: &[Vec<u8>]) -> bool {
+    siblings
+        .iter()
+        .fold(leaf.to_vec(), |hash, sibling| node(&hash, sibling))
+        == root
+}
+
+/// Tree builder used to prepare test data.
+#[cfg(test)]
+pub mod tree {
+    use super::node;
+
+    /// Tree levels from leaves up to the root. Odd node at a level is promoted as is.
+    fn levels(leaves: &[Vec<u8>]) -> Vec<Vec<Vec<u8>>> {
+        let mut levels = vec![leaves.to_vec()];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node(a, b),
+                    [a] => a.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        levels
+    }
+
+    pub fn root(leaves: &[Vec<u8>]) -> Vec<u8> {
+        levels(leaves).last().unwrap()[0].clone()
+    }
+
+    pub fn proof(leaves: &[Vec<u8>], mut index: usize) -> Vec<Vec<u8>> {
+        let mut siblings = vec![];
+        for level in levels(leaves) {
+            if let Some(sibling) = level.get(index ^ 1) {
+                siblings.push(sibling.clone());
+            }
+            index /= 2;
+        }
+        siblings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use near_sdk::AccountId;
+
+    use super::*;
+
+    #[test]
+    fn should_verify_membership() {
+        let leaves: Vec<Vec<u8>> = ["alice", "bob", "carol", "dave", "eve"]
+            .iter()
+            .map(|name| leaf(&AccountId::new_unchecked(name.to_string()), 1))
+            .collect();
+        let root = tree::root(&leaves);
+
+        for i in 0..leaves.len() {
+            assert!(verify(&root, &leaves[i], &tree::proof(&leaves, i)));
+        }
+        assert!(!verify(&root, &leaves[0], &tree::proof(&leaves, 1)));
+    }
+
+    #[test]
+    fn should_bind_weight_to_leaf() {
+        let account = AccountId::new_unchecked("alice".to_string());
+        let leaves = vec![leaf(&account, 5), leaf(&account, 1)];
+        let root = tree::root(&leaves);
+
+        assert!(verify(&root, &leaf(&account, 5), &tree::proof(&leaves, 0)));
+        assert!(!verify(&root, &leaf(&account, 6), &tree::proof(&leaves, 0)));
+    }
+}
@@ -0,0 +1,124 @@
+use num_bigint::BigUint;
+
+use near_sdk::env;
+
+/// Minimal size of an issuer key modulus.
+const MIN_MODULUS_BITS: u64 = 2048;
+
+/// Checks that big-endian modulus & exponent make a usable RSA public key.
+pub fn valid_key(modulus: &[u8], exponent: &[u8]) -> bool {
+    let modulus = BigUint::from_bytes_be(modulus);
+    let exponent = BigUint::from_bytes_be(exponent);
+    modulus.bits() >= MIN_MODULUS_BITS
+        && modulus.bit(0)
+        && exponent.bit(0)
+        && exponent > BigUint::from(1u8)
+        && exponent < modulus
+}
+
+/// Full domain hash of a message: MGF1 with `sha256`, one byte shorter than modulus.
+pub fn full_domain_hash(message: &[u8], modulus_len: usize) -> BigUint {
+    let seed = env::sha256(message);
+    let mut output = vec![];
+    let mut counter: u32 = 0;
+    while output.len() < modulus_len - 1 {
+        output.extend(env::sha256(&[&seed[..], &counter.to_be_bytes()].concat()));
+        counter += 1;
+    }
+    output.truncate(modulus_len - 1);
+    BigUint::from_bytes_be(&output)
+}
+
+/// Checks RSA-FDH signature, e.g. an unblinded blind signature.
+///
+/// Key & signature are big-endian. Key is expected to be valid according to [valid_key].
+pub fn verify(modulus: &[u8], exponent: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let modulus = BigUint::from_bytes_be(modulus);
+    let modulus_len = modulus.to_bytes_be().len();
+    let signature = BigUint::from_bytes_be(signature);
+    signature < modulus
+        && signature.modpow(&BigUint::from_bytes_be(exponent), &modulus)
+            == full_domain_hash(message, modulus_len)
+}
+
+/// Organization side of the protocol. Used to prepare test data.
+#[cfg(test)]
+pub mod issuer {
+    use num_bigint::BigUint;
+
+    const MODULUS: &str = concat!(
+        "d5686e00904d47ce6834f474080132ecdf5238978113d64acf0ade2fd23aef63",
+        "309b3cf8f87a25287fc05772a129d1321d3a4b930caadc3872793999de177e68",
+        "682f52ca2a3eab08fa98ea3cc764b219ba572747ef3b973ff0b5aa519e51bd35",
+        "fad5a1abf5772bd5b97275fddb37435755764b9cd8075aaa4c8e0a9618a93635",
+        "d7dd6bea5c71bc8bbbf127a8494c3a4fd08ed674ca82fc1727de03c011e25eb0",
+        "d9fe4d8f8db3e50f6107e6ec2358b0f9ab5c21fdc741105ad76ca9172750c86e",
+        "25e4f45a42ad9e79b9c9588d03a90fab1f581282664b7f25a0928ebb8eec9dfd",
+        "b310d56280220c28cae0e23e5e3070685e6ef2f20ef620a97ffc294afd506e31",
+    );
+
+    const PRIVATE_EXPONENT: &str = concat!(
+        "5f1078001447f8b6bfc0e72c50ec87ccf0e6658347e2985116cdd69022ec51fb",
+        "3389d3ad6cb2ac9ecfd9d49aa1a4492633d2e61b89470b76b1b7c2ab023e20f7",
+        "4d81d7356b80231c8ad55772dfb7c186a8756e7022e7bec6a2e9aa0755a93fca",
+        "2e04c05223b484785e75d88dcefb5d2a284ddbfcf056c6529a6ba3d79d00b3be",
+        "9439e42801043d3dc13f0d897e07087f0b3c8ef61fe6ecc5044d60774a87a66f",
+        "d558c40a316a9820e5e6e3aca4fa06ba54e1acd00e1857dd55f457b12bef90ed",
+        "812560598a59446633f5d26b4b1b183cc39de7d8d762ecc55bb439100af00492",
+        "d9c8b3b23da9d2233396b2e197b792e106804a5dcded9988a25e55cdc4718889",
+    );
+    pub const PUBLIC_EXPONENT: u32 = 65537;
+
+    pub fn modulus() -> Vec<u8> {
+        BigUint::parse_bytes(MODULUS.as_bytes(), 16)
+            .unwrap()
+            .to_bytes_be()
+    }
+
+    pub fn exponent() -> Vec<u8> {
+        BigUint::from(PUBLIC_EXPONENT).to_bytes_be()
+    }
+
+    fn private_key() -> (BigUint, BigUint) {
+        (
+            BigUint::parse_bytes(MODULUS.as_bytes(), 16).unwrap(),
+            BigUint::parse_bytes(PRIVATE_EXPONENT.as_bytes(), 16).unwrap(),
+        )
+    }
+
+    /// Voter blinds message, issuer signs it without seeing it & voter unblinds the signature.
+    ///
+    /// `blinding` factor should be coprime with the modulus.
+    pub fn blind_sign(message: &[u8], blinding: u64) -> Vec<u8> {
+        let (modulus, private_exponent) = private_key();
+        let r = BigUint::from(blinding);
+        let hash = super::full_domain_hash(message, modulus.to_bytes_be().len());
+        let blinded = hash * r.modpow(&BigUint::from(PUBLIC_EXPONENT), &modulus) % &modulus;
+
+        let blind_signature = blinded.modpow(&private_exponent, &modulus);
+
+        (blind_signature * r.modinv(&modulus).unwrap() % &modulus).to_bytes_be()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::issuer::*;
+    use super::*;
+
+    #[test]
+    fn should_verify_unblinded_signature() {
+        let signature = blind_sign(b"token", 12345);
+
+        assert!(verify(&modulus(), &exponent(), b"token", &signature));
+        assert!(!verify(&modulus(), &exponent(), b"other token", &signature));
+    }
+
+    #[test]
+    fn should_validate_keys() {
+        assert!(valid_key(&modulus(), &exponent()));
+        assert!(!valid_key(&modulus()[1..], &exponent()));
+        assert!(!valid_key(&modulus(), &[1]));
+        assert!(!valid_key(&modulus(), &[4]));
+    }
+}
@@ -0,0 +1,126 @@
+use std::convert::TryInto;
+
+use crate::CandidateId;
+
+/// Adds ranked ballot to pairwise preferences matrix.
+///
+/// Matrix is stored row by row, `matrix[a * candidates + b]` is the number of voters
+/// preferring candidate `a` over candidate `b`.
+/// Ranked candidates are preferred over every candidate ranked below them or not ranked at all.
+pub fn record_ranking(matrix: &mut [u32], candidates: usize, ranking: &[CandidateId]) {
+    // Position of every candidate on the ballot, unranked ones share the last one.
+    let mut positions = vec![ranking.len(); candidates];
+    for (i, candidate) in ranking.iter().enumerate() {
+        positions[usize::from(*candidate)] = i;
+    }
+    for (i, preferred) in ranking.iter().enumerate() {
+        let row = usize::from(*preferred) * candidates;
+        for (other, position) in positions.iter().enumerate() {
+            if *position > i {
+                matrix[row + other] += 1;
+            }
+        }
+    }
+}
+
+/// Computes strengths of the strongest (widest) paths between every pair of candidates.
+pub fn strongest_paths(matrix: &[u32], candidates: usize) -> Vec<u32> {
+    let mut paths = vec![0; candidates * candidates];
+    for a in 0..candidates {
+        for b in 0..candidates {
+            if a != b && matrix[a * candidates + b] > matrix[b * candidates + a] {
+                paths[a * candidates + b] = matrix[a * candidates + b];
+            }
+        }
+    }
+    for i in 0..candidates {
+        for j in 0..candidates {
+            if i == j {
+                continue;
+            }
+            for k in 0..candidates {
+                if i != k && j != k {
+                    let through = paths[j * candidates + i].min(paths[i * candidates + k]);
+                    if through > paths[j * candidates + k] {
+                        paths[j * candidates + k] = through;
+                    }
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// Candidates not beaten by anyone through the strongest paths.
+pub fn winners(paths: &[u32], candidates: usize) -> Vec<CandidateId> {
+    (0..candidates)
+        .filter(|a| {
+            (0..candidates)
+                .all(|b| a == &b || paths[a * candidates + b] >= paths[b * candidates + a])
+        })
+        .map(|a| a.try_into().unwrap())
+        .collect()
+}
+
+/// Candidate preferred over every other candidate by a majority of voters, if any.
+pub fn condorcet_winner(matrix: &[u32], candidates: usize) -> Option<CandidateId> {
+    (0..candidates)
+        .find(|a| {
+            (0..candidates)
+                .all(|b| a == &b || matrix[a * candidates + b] > matrix[b * candidates + a])
+        })
+        .map(|a| a.try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_record_unranked_candidates_as_least_preferred() {
+        let mut matrix = vec![0; 9];
+
+        record_ranking(&mut matrix, 3, &[1]);
+
+        assert_eq!(matrix, vec![0, 0, 0, 1, 0, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn should_find_condorcet_winner() {
+        let mut matrix = vec![0; 9];
+        record_ranking(&mut matrix, 3, &[0, 1, 2]);
+        record_ranking(&mut matrix, 3, &[1, 0, 2]);
+        record_ranking(&mut matrix, 3, &[0, 2, 1]);
+
+        assert_eq!(condorcet_winner(&matrix, 3), Some(0));
+        assert_eq!(winners(&strongest_paths(&matrix, 3), 3), vec![0]);
+    }
+
+    #[test]
+    fn should_resolve_condorcet_cycle() {
+        let mut matrix = vec![0; 9];
+        let ballots: [(&[CandidateId], usize); 3] =
+            [(&[0, 1, 2], 4), (&[1, 2, 0], 3), (&[2, 0, 1], 2)];
+        for (ranking, count) in ballots {
+            for _ in 0..count {
+                record_ranking(&mut matrix, 3, ranking);
+            }
+        }
+
+        let paths = strongest_paths(&matrix, 3);
+
+        assert_eq!(condorcet_winner(&matrix, 3), None);
+        assert_eq!(paths, vec![0, 6, 6, 5, 0, 7, 5, 5, 0]);
+        assert_eq!(winners(&paths, 3), vec![0]);
+    }
+
+    #[test]
+    fn should_report_all_tied_winners() {
+        let mut matrix = vec![0; 4];
+        record_ranking(&mut matrix, 2, &[0, 1]);
+        record_ranking(&mut matrix, 2, &[1, 0]);
+
+        assert_eq!(condorcet_winner(&matrix, 2), None);
+        assert_eq!(winners(&strongest_paths(&matrix, 2), 2), vec![0, 1]);
+    }
+}
@@ -0,0 +1,167 @@
+use std::cmp::Reverse;
+use std::convert::TryInto;
+
+use crate::irv::{tied_with, Round};
+use crate::{CandidateId, VOTE_SCALE as SCALE};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Status {
+    Continuing,
+    Elected,
+    Excluded,
+}
+
+/// Runs Single Transferable Vote count over ranked ballots, grouped by ranking with their counts.
+///
+/// Uses Droop quota and weighted inclusive Gregory method for surplus transfers.
+/// One candidate reaching the quota is elected per round, otherwise the weakest one is excluded.
+/// When continuing candidates can only just fill remaining seats, all of them are elected.
+/// Ties for election favour the candidate with the lowest `rank`,
+/// ties for exclusion drop the one with the highest.
+///
+/// Returns elected candidates in order of election, quota (in whole votes) and counting rounds.
+/// Round tallies are in [VOTE_SCALE](crate::VOTE_SCALE) units.
+pub fn single_transferable_vote(
+    candidates: usize,
+    seats: usize,
+    ballots: &[(Vec<CandidateId>, u128)],
+    rank: &[usize],
+) -> (Vec<CandidateId>, u128, Vec<Round>) {
+    let total: u128 = ballots.iter().map(|(_, count)| count).sum();
+    let quota = total / (seats as u128 + 1) + 1;
+    let mut elected = vec![];
+    let mut rounds = vec![];
+    if total == 0 {
+        return (elected, quota, rounds);
+    }
+    let mut status = vec![Status::Continuing; candidates];
+    // Identical ballots follow the same path, so they share weight.
+    let mut weights = vec![SCALE; ballots.len()];
+
+    while elected.len() < seats {
+        let mut counts = vec![0u128; candidates];
+        let mut exhausted = 0;
+        let mut current = vec![None; ballots.len()];
+        for (i, (ranking, count)) in ballots.iter().enumerate() {
+            match ranking
+                .iter()
+                .find(|c| status[usize::from(**c)] == Status::Continuing)
+            {
+                Some(choice) => {
+                    counts[usize::from(*choice)] += weights[i] * count;
+                    current[i] = Some(*choice);
+                }
+                None => exhausted += weights[i] * count,
+            }
+        }
+        let tallies: Vec<(CandidateId, u128)> = (0..candidates)
+            .filter(|i| status[*i] == Status::Continuing)
+            .map(|i| (i.try_into().unwrap(), counts[i]))
+            .collect();
+
+        if tallies.len() + elected.len() <= seats {
+            let mut remaining = tallies.clone();
+            remaining.sort_by(|(_, a), (_, b)| b.cmp(a));
+            let remaining: Vec<CandidateId> = remaining.into_iter().map(|(c, _)| c).collect();
+            elected.extend(&remaining);
+            rounds.push(Round {
+                tallies,
+                exhausted,
+                elected: remaining,
+                eliminated: None,
+                tied: vec![],
+            });
+            break;
+        }
+
+        let (leader, leader_votes) = *tallies
+            .iter()
+            .max_by_key(|(candidate, votes)| (*votes, Reverse(rank[usize::from(*candidate)])))
+            .unwrap();
+        if leader_votes >= quota * SCALE {
+            let surplus = leader_votes - quota * SCALE;
+            for (i, choice) in current.iter().enumerate() {
+                if *choice == Some(leader) {
+                    weights[i] = weights[i] * surplus / leader_votes;
+                }
+            }
+            status[usize::from(leader)] = Status::Elected;
+            elected.push(leader);
+            rounds.push(Round {
+                tied: tied_with(&tallies, leader_votes),
+                tallies,
+                exhausted,
+                elected: vec![leader],
+                eliminated: None,
+            });
+        } else {
+            let (excluded, fewest) = *tallies
+                .iter()
+                .max_by_key(|(candidate, votes)| (Reverse(*votes), rank[usize::from(*candidate)]))
+                .unwrap();
+            status[usize::from(excluded)] = Status::Excluded;
+            rounds.push(Round {
+                tied: tied_with(&tallies, fewest),
+                tallies,
+                exhausted,
+                elected: vec![],
+                eliminated: Some(excluded),
+            });
+        }
+    }
+    (elected, quota, rounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_transfer_surplus() {
+        let ballots = vec![(vec![0, 1], 8), (vec![2], 3), (vec![3, 2], 2)];
+
+        let (elected, quota, rounds) = single_transferable_vote(4, 2, &ballots, &[0, 1, 2, 3]);
+
+        assert_eq!(quota, 5);
+        assert_eq!(elected, vec![0, 2]);
+        assert_eq!(rounds.len(), 3);
+        assert_eq!(rounds[0].elected, vec![0]);
+        assert_eq!(
+            rounds[1].tallies,
+            vec![(1, 3 * SCALE), (2, 3 * SCALE), (3, 2 * SCALE)]
+        );
+        assert_eq!(rounds[1].eliminated, Some(3));
+        assert_eq!(rounds[2].tallies, vec![(1, 3 * SCALE), (2, 5 * SCALE)]);
+        assert_eq!(rounds[2].elected, vec![2]);
+    }
+
+    #[test]
+    fn should_elect_remaining_candidates_when_seats_left() {
+        let ballots = vec![(vec![0], 2), (vec![1], 1), (vec![2, 1], 1)];
+
+        let (elected, _, rounds) = single_transferable_vote(3, 2, &ballots, &[0, 1, 2]);
+
+        assert_eq!(elected, vec![0, 1]);
+        assert_eq!(rounds[1].eliminated, Some(2));
+        assert_eq!(rounds.last().unwrap().elected, vec![1]);
+    }
+
+    #[test]
+    fn should_break_ties_by_rank() {
+        let ballots = vec![(vec![0], 2), (vec![1], 2), (vec![2], 1), (vec![3], 1)];
+
+        let (elected, _, rounds) = single_transferable_vote(4, 1, &ballots, &[3, 0, 1, 2]);
+
+        assert_eq!(rounds[0].eliminated, Some(3));
+        assert_eq!(rounds[0].tied, vec![2, 3]);
+        assert_eq!(elected, vec![1]);
+    }
+
+    #[test]
+    fn should_not_elect_anyone_without_ballots() {
+        let (elected, _, rounds) = single_transferable_vote(3, 2, &[], &[0, 1, 2]);
+
+        assert!(elected.is_empty());
+        assert!(rounds.is_empty());
+    }
+}
@@ -0,0 +1,73 @@
+use std::convert::TryInto;
+
+use ed25519_dalek::{Signature, VerifyingKey};
+
+/// Checks that bytes make a usable ed25519 public key.
+pub fn valid_key(key: &[u8]) -> bool {
+    decode_key(key).is_some_and(|key| !key.is_weak())
+}
+
+/// Checks ed25519 signature of a voucher message.
+///
+/// Strict verification is used, so signature is not malleable.
+pub fn verify(key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let key = match decode_key(key) {
+        Some(key) => key,
+        None => return false,
+    };
+    let signature: [u8; 64] = match signature.try_into() {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    key.verify_strict(message, &Signature::from_bytes(&signature))
+        .is_ok()
+}
+
+fn decode_key(key: &[u8]) -> Option<VerifyingKey> {
+    VerifyingKey::from_bytes(key.try_into().ok()?).ok()
+}
+
+/// Organization side of the protocol. Used to prepare test data.
+#[cfg(test)]
+pub mod signer {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    pub fn public_key(secret: &[u8; 32]) -> Vec<u8> {
+        SigningKey::from_bytes(secret)
+            .verifying_key()
+            .to_bytes()
+            .to_vec()
+    }
+
+    pub fn sign(secret: &[u8; 32], message: &[u8]) -> Vec<u8> {
+        SigningKey::from_bytes(secret)
+            .sign(message)
+            .to_bytes()
+            .to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::signer::*;
+    use super::*;
+
+    #[test]
+    fn should_verify_signature() {
+        let secret = [7; 32];
+        let key = public_key(&secret);
+        let signature = sign(&secret, b"voucher");
+
+        assert!(verify(&key, b"voucher", &signature));
+        assert!(!verify(&key, b"other voucher", &signature));
+        assert!(!verify(&public_key(&[8; 32]), b"voucher", &signature));
+        assert!(!verify(&key, b"voucher", &signature[1..]));
+    }
+
+    #[test]
+    fn should_reject_invalid_keys() {
+        assert!(valid_key(&public_key(&[7; 32])));
+        assert!(!valid_key(&[0; 32]));
+        assert!(!valid_key(&public_key(&[7; 32])[1..]));
+    }
+}
@@ -12,7 +12,8 @@ type ElectionData = {
 
 type Candidate = {
   name: string;
-  votes: string;
+  /** Missing while election results are hidden. */
+  votes?: string;
 };
 
 type ElectionView = {
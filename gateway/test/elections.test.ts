@@ -27,7 +27,8 @@ type ElectionData = {
 
 type Candidate = {
   name: string;
-  votes: string;
+  /** Missing while election results are hidden. */
+  votes?: string;
 };
 
 type ElectionView = {
@@ -24,8 +24,8 @@ function ElectionRow({
     contract.getElection(BigInt(electionId)).then((e) => setElection(e));
   }
   if (election && helpMessage === "Loading...") {
-    contract.haveVoted(BigInt(electionId)).then((voted) => {
-      if (voted) {
+    contract.haveVoted(BigInt(electionId)).then((status) => {
+      if (status === "direct") {
         setHelpMessage("You've already voted.");
       } else if (toMillis(election.start) > Date.now()) {
         setHelpMessage("Election hasn't started yet.");
@@ -48,8 +48,15 @@ function ElectionRow({
       </TableCell>
       <TableCell>
         {election?.candidates.map(({ name, votes }, i) => (
-          <Typography key={name}>{`${name} (${votes})`} </Typography>
+          <Typography key={name}>
+            {votes === undefined ? name : `${name} (${votes})`}
+          </Typography>
         )) || []}
+        {election?.candidates.some(({ votes }) => votes === undefined) && (
+          <Typography variant="caption" color="text.secondary">
+            Results hidden
+          </Typography>
+        )}
       </TableCell>
       <TableCell>
         <Tooltip title={canVote ? "" : helpMessage}>
@@ -14,7 +14,8 @@ export function getElectionsContract(account: Account): Elections {
 
 type Candidate = {
   name: string;
-  votes: string;
+  /** Missing while election results are hidden. */
+  votes?: string;
 };
 
 export type Election = {
@@ -25,6 +26,9 @@ export type Election = {
   candidates: Candidate[];
 };
 
+/** How the caller's ballot is cast in an election. */
+export type VoteStatus = "not_voted" | "direct" | "delegated";
+
 export class Elections {
   organizationId: string;
   contract: ElectionsContract;
@@ -57,7 +61,7 @@ export class Elections {
     });
   }
 
-  haveVoted(electionId: bigint): Promise<boolean> {
+  haveVoted(electionId: bigint): Promise<VoteStatus> {
     return this.contract.have_voted({
       organization_id: this.organizationId,
       election_id: electionId.toString(),
@@ -82,5 +86,5 @@ interface ElectionsContract {
   have_voted(args: {
     organization_id: string;
     election_id: string;
-  }): Promise<boolean>;
+  }): Promise<VoteStatus>;
 }